@@ -1,11 +1,26 @@
-use super::game_data::{EventOutcome, generate_event};
-use super::lotus_widget::LotusWidget;
-use super::procedural::EventDomain;
-use super::{FloatingText, LotusApp};
+use super::game_data::{
+    EventOption, EventOutcome, EventSource, GenerationMode, bankruptcy_event,
+    generate_event_traced, grace_event, review_event,
+};
+use super::icons::ICON_SIZE;
+use super::lotus_widget::{BoardView, LotusWidget};
+use super::number_format::{amount, signed_amount, thousands};
+use super::procedural::library::{ChoiceType, Severity};
+use super::procedural::memory::{CHOICE_MEMORY_SIZE, ChoiceMemory};
+use super::procedural::time_context::TimeContext;
+use super::procedural::{EventDomain, PlayerIdentity, Pronouns};
+use super::save::{self, SAVE_PATH};
+use super::scenario::DEFAULT_START_PETAL;
+use super::config::GuanxiOverflow;
+use super::engine::Resolution;
+use super::stats::RunStats;
+use super::{FloatingText, GameState, LotusApp, TIER_COUNT, TierBanner};
 use eframe::egui::{
-    self, Align2, Area, Color32, Id, Order, Pos2, Rect, RichText, ScrollArea, Window, vec2,
+    self, Align2, Area, Color32, Id, Key, Order, Pos2, Rect, RichText, ScrollArea, Window, vec2,
 };
 use rand::Rng;
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 
 impl LotusApp {
     // Add a queue for floating text animations
@@ -25,18 +40,94 @@ const TIER_C_MAX: i32 = 399; // Tier C is 200 - 399
 const TIER_B_MAX: i32 = 749; // Tier B is 400 - 749
 const TIER_A_MAX: i32 = 999; // Tier A is 750 - 999
 // Tier A+ is anything > 999
+/// Top SCS of every tier but the last, innermost first
+const TIER_MAXES: [i32; TIER_COUNT - 1] = [TIER_D_MAX, TIER_C_MAX, TIER_B_MAX, TIER_A_MAX];
+
+/// The tier `scs` falls in, 0 (D) to `TIER_COUNT - 1` (A+)
+pub(crate) fn tier_for_scs(scs: i32) -> usize {
+    TIER_MAXES.iter().take_while(|max| scs > **max).count()
+}
+
+/// The SCS range of the tier `scs` falls in
+fn tier_band(scs: i32) -> RangeInclusive<i32> {
+    let tier = tier_for_scs(scs);
+    let low = match tier {
+        0 => i32::MIN,
+        _ => TIER_MAXES[tier - 1] + 1,
+    };
+    low..=TIER_MAXES.get(tier).copied().unwrap_or(i32::MAX)
+}
+
+/// Adds `change` to a guanxi stat, holding gains at `cap` (0 for none) and
+/// returning the points gained past it. A stat already over its cap keeps
+/// its value; losses always apply.
+fn add_capped(value: &mut u32, change: i32, cap: u32) -> u32 {
+    let raised = value.saturating_add_signed(change);
+    if cap == 0 || change <= 0 || raised <= cap {
+        *value = raised;
+        return 0;
+    }
+    let held = (*value).max(cap);
+    *value = held;
+    raised - held
+}
+
+// --- Tier Banner Timing (seconds) ---
+const TIER_BANNER_SLIDE: f64 = 0.4;
+const TIER_BANNER_HOLD: f64 = 2.0;
+const TIER_BANNER_FADE: f64 = 0.8;
+const TIER_BANNER_SLIDE_DISTANCE: f32 = 80.0;
+
+// --- Board Snapshot ---
+const BOARD_SNAPSHOT_PATH: &str = "lotus_board.svg";
+const BOARD_SNAPSHOT_SIZE: f32 = 800.0;
+
+// --- Event Shortcuts ---
+/// Number keys that pick the first nine event options
+const OPTION_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
 
-// --- NEW: Life Stage Definitions ---
-const AGE_STAGE_2: u32 = 26; // Early Career (26-40)
-const AGE_STAGE_3: u32 = 41; // Mid-Career (41-55)
-const AGE_STAGE_4: u32 = 56; // Seniority (56+)
+// --- Context Tracking ---
+/// Number of resolved procedural domains remembered for the repetition filter
+pub const RECENT_DOMAIN_WINDOW: usize = 15;
+/// Turns before an encountered situation becomes eligible again
+pub const ENCOUNTER_COOLDOWN_TURNS: u64 = 30;
+/// How many recent result lines procedural events avoid repeating
+pub const RECENT_RESULT_WINDOW: usize = 6;
+
+/// The choice behind an applied outcome, summarised in its history entry
+struct ResolvedChoice<'a> {
+    event_title: &'a str,
+    archetype: Option<ChoiceType>,
+    succeeded: Option<bool>, // None for choices without risk
+    result: &'a str,
+}
 
 impl LotusApp {
     /// Returns true if the petal is one of the SCS review spaces
-    fn is_review_petal(&self, petal_index: usize) -> bool {
+    pub(crate) fn is_review_petal(&self, petal_index: usize) -> bool {
         petal_index == 0 || petal_index == 4 || petal_index == 8
     }
 
+    /// A game may start on any petal of the board except a review petal
+    pub fn is_valid_start_petal(&self, petal_index: usize) -> bool {
+        petal_index < self.num_petals_per_tier && !self.is_review_petal(petal_index)
+    }
+
+    /// The player's position counted across every tier's petals
+    fn player_total_index(&self) -> usize {
+        self.player_tier * self.num_petals_per_tier + self.player_petal
+    }
+
     /// Formats EventOutcome deltas as a compact string (e.g., "+10 SCS, -5 Finance")
     fn format_stat_deltas(outcome: &EventOutcome) -> String {
         let mut deltas = Vec::new();
@@ -45,7 +136,10 @@ impl LotusApp {
             deltas.push(format!("{:+} SCS", outcome.scs_change));
         }
         if outcome.finance_change != 0 {
-            deltas.push(format!("{:+} Finance", outcome.finance_change));
+            deltas.push(format!(
+                "{} Finance",
+                signed_amount(outcome.finance_change.into())
+            ));
         }
         if outcome.career_level_change != 0 {
             deltas.push(format!("{:+} Career", outcome.career_level_change));
@@ -59,6 +153,9 @@ impl LotusApp {
         if outcome.guanxi_party_change != 0 {
             deltas.push(format!("{:+} Party", outcome.guanxi_party_change));
         }
+        if outcome.favor_change != 0 {
+            deltas.push(format!("{:+} Favors", outcome.favor_change));
+        }
 
         if deltas.is_empty() {
             String::from("No change")
@@ -67,18 +164,121 @@ impl LotusApp {
         }
     }
 
-    /// Safely applies all stat changes from an EventOutcome
-    fn apply_outcome(&mut self, outcome: &EventOutcome, ui_rect: Rect, result_text: &str) {
+    /// What choosing an option would do, without applying it or rolling:
+    /// the success outcome, the failure outcome (None when failure has no
+    /// effect), and the risk of failure in percent.
+    pub fn preview_outcome(&self, option: &EventOption) -> (EventOutcome, Option<EventOutcome>, u8) {
+        let failure_outcome = if option.risk_chance > 0 {
+            option.failure_outcome.clone()
+        } else {
+            None
+        };
+        (option.success_outcome.clone(), failure_outcome, option.risk_chance)
+    }
+
+    /// Safely applies every effect of a resolved choice, logging a single
+    /// history entry with the net change
+    fn apply_outcome(&mut self, effects: &[EventOutcome], choice: &ResolvedChoice) {
+        let outcome = &EventOutcome::net(effects);
+
         // --- Log to History ---
-        let stat_deltas = Self::format_stat_deltas(outcome);
-        self.history
-            .push(format!("[Age {}] {} [{}]", self.player_age, result_text, stat_deltas));
+        let mut how = Vec::new();
+        if let Some(archetype) = &choice.archetype {
+            how.push(archetype.as_str());
+        }
+        match choice.succeeded {
+            Some(true) => how.push("succeeded"),
+            Some(false) => how.push("failed"),
+            None => {}
+        }
+        let how = if how.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", how.join(", "))
+        };
+        self.history.push(format!(
+            "[Age {}] {}{}: {} [{}]",
+            self.player_age,
+            choice.event_title,
+            how,
+            choice.result,
+            Self::format_stat_deltas(outcome)
+        ));
         if self.history.len() > 100 {
             // Keep history from getting too long
             self.history.remove(0);
         }
 
-        // --- Floating Text ---
+        self.social_credit_score += outcome.scs_change;
+        self.scs_since_review += outcome.scs_change;
+
+        // Finances can't fall below the configured floor; hitting it means bankruptcy
+        let new_finances = self.finances.saturating_add(outcome.finance_change);
+        if outcome.finance_change < 0 && new_finances < self.config.finance_floor {
+            self.finances = self.config.finance_floor;
+            self.bankruptcy_pending = true;
+        } else {
+            self.finances = new_finances;
+        }
+        self.bank_finance_overflow();
+
+        // Use saturating_add for u32 values to prevent overflow/underflow
+        self.career_level = self
+            .career_level
+            .saturating_add_signed(outcome.career_level_change);
+        self.apply_guanxi_changes(outcome);
+        self.favors = self.favors.saturating_add_signed(outcome.favor_change);
+    }
+
+    /// Applies an outcome's guanxi changes under the configured caps. Points
+    /// gained past a cap become SCS or are dropped, per `GuanxiCaps::overflow`.
+    pub(crate) fn apply_guanxi_changes(&mut self, outcome: &EventOutcome) {
+        let caps = self.config.guanxi_caps;
+        let family = add_capped(&mut self.guanxi_family, outcome.guanxi_family_change, caps.family);
+        let network = add_capped(
+            &mut self.guanxi_network,
+            outcome.guanxi_network_change,
+            caps.network,
+        );
+        let party = add_capped(&mut self.guanxi_party, outcome.guanxi_party_change, caps.party);
+        let overflow = family + network + party;
+        if overflow == 0 {
+            return;
+        }
+        match caps.overflow {
+            GuanxiOverflow::Scs => {
+                let bonus = (overflow as i32).saturating_mul(caps.scs_per_point);
+                self.social_credit_score += bonus;
+                self.scs_since_review += bonus;
+                self.history.push(format!(
+                    "[Age {}] Owed favors: {:+} SCS for guanxi past its cap",
+                    self.player_age, bonus
+                ));
+            }
+            GuanxiOverflow::Clamp => {
+                log::debug!("{} guanxi point(s) past the caps were dropped", overflow);
+            }
+        }
+    }
+
+    /// The legacy score as a signed amount for display
+    fn legacy_amount(&self) -> i64 {
+        i64::try_from(self.legacy).unwrap_or(i64::MAX)
+    }
+
+    /// Moves any finances above the configured cap into the legacy score
+    pub(crate) fn bank_finance_overflow(&mut self) {
+        let cap = self.config.finance_cap;
+        if cap > 0 && self.finances > cap {
+            let overflow = (self.finances - cap) as u64;
+            self.legacy = self.legacy.saturating_add(overflow);
+            self.finances = cap;
+            log::info!("Banked {} ¥ above the finance cap as legacy", overflow);
+        }
+    }
+
+    /// Floats an outcome's SCS and finance changes up from the top of `ui_rect`
+    fn add_outcome_floaters(&mut self, outcome: &EventOutcome, ui_rect: Rect) {
         let base_pos = ui_rect.center_top();
         if outcome.scs_change != 0 {
             let text = format!("{} SCS", outcome.scs_change);
@@ -90,7 +290,7 @@ impl LotusApp {
             self.add_floating_text(text, base_pos, color);
         }
         if outcome.finance_change != 0 {
-            let text = format!("{} ¥", outcome.finance_change);
+            let text = format!("{} ¥", signed_amount(outcome.finance_change.into()));
             let color = if outcome.finance_change > 0 {
                 Color32::GOLD
             } else {
@@ -99,39 +299,33 @@ impl LotusApp {
             self.add_floating_text(text, Pos2::new(base_pos.x + 20.0, base_pos.y), color);
         }
         // ... add more for other stats if desired
+    }
 
-        self.social_credit_score += outcome.scs_change;
-        self.finances += outcome.finance_change;
+    /// Width of the event and error modals for the current screen size
+    fn event_window_width(&self, ctx: &egui::Context) -> f32 {
+        let config = &self.config;
+        let width = ctx.content_rect().width() * config.event_window_width_fraction;
+        // Not `clamp`, which panics if a bad config puts min above max
+        width
+            .max(config.event_window_min_width)
+            .min(config.event_window_max_width)
+    }
 
-        // Use saturating_add for u32 values to prevent overflow/underflow
-        self.career_level = self
-            .career_level
-            .saturating_add_signed(outcome.career_level_change);
-        self.guanxi_family = self
-            .guanxi_family
-            .saturating_add_signed(outcome.guanxi_family_change);
-        self.guanxi_network = self
-            .guanxi_network
-            .saturating_add_signed(outcome.guanxi_network_change);
-        self.guanxi_party = self
-            .guanxi_party
-            .saturating_add_signed(outcome.guanxi_party_change);
+    /// Display name for a tier index
+    fn tier_name(tier: usize) -> &'static str {
+        match tier {
+            0 => "D",
+            1 => "C",
+            2 => "B",
+            3 => "A",
+            _ => "A+",
+        }
     }
 
     /// Checks the player's SCS and updates their tier if needed.
     /// Returns true if the tier changed.
     fn update_player_tier_from_scs(&mut self) -> bool {
-        let new_tier = if self.social_credit_score <= TIER_D_MAX {
-            0 // Tier D
-        } else if self.social_credit_score <= TIER_C_MAX {
-            1 // Tier C
-        } else if self.social_credit_score <= TIER_B_MAX {
-            2 // Tier B
-        } else if self.social_credit_score <= TIER_A_MAX {
-            3 // Tier A
-        } else {
-            4 // Tier A+
-        };
+        let new_tier = tier_for_scs(self.social_credit_score);
 
         if new_tier != self.player_tier {
             self.player_tier = new_tier;
@@ -149,19 +343,15 @@ impl LotusApp {
         self.history.push(age_up_msg.clone());
         self.last_event_result = Some(age_up_msg);
         self.update_life_stage(); // Check if this new age triggers a new life stage
+        if self.player_age >= self.config.retirement_age {
+            self.history.push(format!("You retired at {}.", self.player_age));
+            self.game_state = GameState::Summary;
+        }
     }
 
     /// Updates the player's life stage based on their new age.
     fn update_life_stage(&mut self) {
-        let new_stage = if self.player_age >= AGE_STAGE_4 {
-            4
-        } else if self.player_age >= AGE_STAGE_3 {
-            3
-        } else if self.player_age >= AGE_STAGE_2 {
-            2
-        } else {
-            1
-        };
+        let new_stage = self.config.life_stage_for_age(self.player_age);
 
         if new_stage != self.life_stage {
             self.life_stage = new_stage;
@@ -174,33 +364,824 @@ impl LotusApp {
         }
     }
 
-    /// Updates context tracking after an event is resolved
-    pub fn update_event_context(&mut self, domain: EventDomain, situation_id: String) {
-        // Update domain history
+    /// Domains of recently resolved procedural events, oldest first (newest at the back)
+    pub fn recent_domains(&self) -> &VecDeque<EventDomain> {
+        &self.recent_event_domains
+    }
+
+    /// Pushes a resolved domain onto the recent-domain window, dropping the oldest entries
+    fn record_recent_domain(&mut self, domain: EventDomain) {
         self.recent_event_domains.push_back(domain);
-        if self.recent_event_domains.len() > 15 {
+        while self.recent_event_domains.len() > RECENT_DOMAIN_WINDOW {
             self.recent_event_domains.pop_front();
         }
+    }
 
-        // Update encounter tracking
-        self.encounter_history.insert(situation_id.clone());
-        self.encounter_map.insert(situation_id, self.event_counter);
-        self.event_counter += 1;
-
-        // Clean old encounters (older than 30 events)
-        let cutoff = self.event_counter.saturating_sub(30);
-        self.encounter_map.retain(|id, &mut counter| {
-            let keep = counter >= cutoff;
-            if !keep {
-                self.encounter_history.remove(id);
+    /// Writes the current board to an SVG file and reports the result
+    fn export_board_snapshot(&mut self) {
+        let widget = LotusWidget::new(
+            TIER_COUNT,
+            self.num_petals_per_tier,
+            self.player_total_index(),
+            &self.board_theme,
+            self.config.widget_style,
+        );
+        let msg = match std::fs::write(BOARD_SNAPSHOT_PATH, widget.to_svg(BOARD_SNAPSHOT_SIZE)) {
+            Ok(()) => format!("Board snapshot saved to {}.", BOARD_SNAPSHOT_PATH),
+            Err(e) => {
+                log::error!("Failed to write board snapshot: {}", e);
+                format!("Could not save board snapshot: {}", e)
+            }
+        };
+        self.last_event_result = Some(msg);
+    }
+
+    /// Sets the starting stats and petal from the selected scenario preset.
+    /// Tier and life stage are derived from the preset's SCS and age.
+    pub(crate) fn apply_scenario(&mut self) {
+        // A fresh run, so nothing has been seen yet
+        self.clear_encounter_history();
+        let Some(scenario) = self.scenarios.get(self.selected_scenario) else {
+            return;
+        };
+        // Ranged stats are rolled in a fixed order, so a seeded run replays them.
+        // An SCS range straddling a tier boundary stays in its minimum's tier.
+        let mut rng = self.rng.borrow_mut();
+        let scs_band = tier_band(scenario.social_credit_score.min());
+        self.social_credit_score = scenario
+            .social_credit_score
+            .roll(&mut *rng)
+            .clamp(*scs_band.start(), *scs_band.end());
+        self.finances = scenario.finances.roll(&mut *rng);
+        self.career_level = scenario.career_level.roll(&mut *rng);
+        self.guanxi_family = scenario.guanxi_family.roll(&mut *rng);
+        self.guanxi_network = scenario.guanxi_network.roll(&mut *rng);
+        self.guanxi_party = scenario.guanxi_party.roll(&mut *rng);
+        drop(rng);
+        self.favors = self.config.starting_favors;
+        self.player_age = scenario.player_age;
+        self.life_stage = self.config.life_stage_for_age(scenario.player_age);
+        self.history.push(format!("Scenario: {}", scenario.name));
+        if scenario.rolls_stats() {
+            self.history.push(format!(
+                "Starting stats: SCS {}, ¥{}, career {}, guanxi {}/{}/{}",
+                self.social_credit_score,
+                self.finances,
+                self.career_level,
+                self.guanxi_family,
+                self.guanxi_network,
+                self.guanxi_party
+            ));
+        }
+        self.player_petal = if self.is_valid_start_petal(scenario.start_petal) {
+            scenario.start_petal
+        } else {
+            log::warn!(
+                "Scenario '{}' starts on petal {}, which is off the board or a review petal",
+                scenario.name,
+                scenario.start_petal
+            );
+            DEFAULT_START_PETAL
+        };
+        self.update_player_tier_from_scs();
+        self.bank_finance_overflow();
+    }
+
+    /// Clears everything that belongs to a single run, ready for a scenario
+    /// to be applied. Identity and settings are kept.
+    fn reset_run(&mut self) {
+        self.legacy = 0;
+        self.bankruptcy_pending = false;
+        self.used_grace = false;
+        self.scs_since_review = 0;
+        self.turn = 0;
+        self.saved_turn = None;
+        self.current_event = None;
+        self.generation_error = None;
+        self.last_event_result = None;
+        self.last_event_source = None;
+        self.generation_trace = None;
+        self.floating_texts.clear();
+        self.tier_banner = None;
+        self.history.clear();
+        self.recent_event_domains.clear();
+        self.clear_encounter_history();
+        self.handcrafted_seen.clear();
+        self.last_high_severity_turn = None;
+        self.choice_memory.clear();
+        self.recent_results.clear();
+        self.choice_cooldowns.clear();
+        self.run_stats = RunStats::default();
+        self.stat_history.clear();
+        self.stats_open = false;
+    }
+
+    /// Starts a fresh run from the selected scenario, keeping whatever
+    /// `carry_over` selects from the run that just ended
+    pub fn new_game_plus(&mut self) {
+        let carry = self.carry_over;
+        let final_finances = self.finances;
+        let favors = self.favors;
+        let legacy = self.legacy;
+        let seen = std::mem::take(&mut self.encounter_map);
+
+        self.reset_run();
+        self.history.push("New Game+".to_string());
+        self.apply_scenario();
+        if final_finances > 0 {
+            // Never more than a capped run could have ended with
+            let share = f64::from(final_finances) * f64::from(carry.finance_fraction);
+            let mut carried = share as i32;
+            if self.config.finance_cap > 0 {
+                carried = carried.min(self.config.finance_cap);
+            }
+            self.finances = self.finances.saturating_add(carried);
+        }
+        if carry.legacy {
+            self.legacy = self.legacy.saturating_add(legacy);
+        }
+        self.bank_finance_overflow();
+        if carry.favors {
+            self.favors = favors;
+        }
+        if carry.encounter_history {
+            // Stamped at the new turn 0, so they age out like any recent encounter
+            self.encounter_map = seen.into_keys().map(|id| (id, 0)).collect();
+        }
+        self.game_state = GameState::Playing;
+    }
+
+    /// End of run: final standing plus the New Game+ carry-over choices
+    fn show_run_summary(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        Window::new(RichText::new("Retirement").strong())
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} retired at {} after {} turns.",
+                    self.player_identity.name, self.player_age, self.turn
+                ));
+                ui.label(format!(
+                    "Tier {}, Social Credit {}",
+                    Self::tier_name(self.player_tier),
+                    self.social_credit_score
+                ));
+                ui.label(format!("Finances: {} ¥", amount(self.finances.into())));
+                if self.legacy > 0 {
+                    ui.label(format!("Legacy: {} ¥", amount(self.legacy_amount())));
+                }
+                ui.label(format!(
+                    "Risky choices: {} succeeded, {} failed",
+                    self.run_stats.successes, self.run_stats.failures
+                ));
+
+                ui.separator();
+                ui.label(RichText::new("Carry into New Game+").strong());
+                ui.add(
+                    egui::Slider::new(&mut self.carry_over.finance_fraction, 0.0..=1.0)
+                        .text("of final finances"),
+                );
+                let seen = self.encounter_count();
+                ui.checkbox(
+                    &mut self.carry_over.encounter_history,
+                    format!("Recently seen situations ({})", seen),
+                );
+                ui.checkbox(
+                    &mut self.carry_over.favors,
+                    format!("Unspent favors ({})", self.favors),
+                );
+                let legacy = format!("Legacy ({} ¥)", amount(self.legacy_amount()));
+                ui.checkbox(&mut self.carry_over.legacy, legacy);
+                ui.label("Scenario:");
+                for (index, scenario) in self.scenarios.iter().enumerate() {
+                    ui.radio_value(&mut self.selected_scenario, index, &scenario.name);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("New Game+").clicked() {
+                        self.new_game_plus();
+                    }
+                    if ui.button("Main Menu").clicked() {
+                        self.reset_run();
+                        self.game_state = GameState::MainMenu;
+                    }
+                });
+            });
+    }
+
+    /// Title screen shown before any game is started or loaded.
+    fn show_main_menu(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        Window::new(RichText::new("Lotus").strong())
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("New Game").clicked() {
+                        self.menu_message = None;
+                        self.game_state = GameState::NewGame;
+                    }
+                    if ui
+                        .add_enabled(save::save_exists(), egui::Button::new("Load"))
+                        .clicked()
+                    {
+                        self.load_game();
+                    }
+                    if ui.button("Settings").clicked() {
+                        self.game_state = GameState::Settings;
+                    }
+                    if ui.button("Quit").clicked() {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                });
+                if let Some(message) = &self.menu_message {
+                    ui.separator();
+                    ui.label(message);
+                }
+            });
+    }
+
+    /// Preferences reachable from the main menu.
+    fn show_settings(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        Window::new(RichText::new("Settings").strong())
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.show_status_bar, "Show status bar");
+                ui.checkbox(&mut self.show_event_source, "Mark procedural events");
+                ui.checkbox(&mut self.stats_dashboard, "Stats dashboard")
+                    .on_hover_text("A summary of your own choices. Stays on this computer.");
+                ui.checkbox(&mut self.still_board, "Still board")
+                    .on_hover_text("No board animations, so screenshots come out the same.");
+                let mode = self.generation_mode;
+                egui::ComboBox::from_label("Events")
+                    .selected_text(mode.label())
+                    .show_ui(ui, |ui| {
+                        for option in GenerationMode::ALL {
+                            ui.selectable_value(&mut self.generation_mode, option, option.label());
+                        }
+                    });
+                if self.generation_mode != mode {
+                    log::info!("Event generation: {}", self.generation_mode.label());
+                }
+                ui.checkbox(&mut self.debug_tools, "Developer tools");
+                if cfg!(debug_assertions) && self.debug_tools {
+                    ui.checkbox(&mut self.include_drafts, "Generate draft situations")
+                        .on_hover_text("Lets situations marked draft appear in play.");
+                }
+                ui.separator();
+                if ui.button("Back").clicked() {
+                    self.game_state = GameState::MainMenu;
+                }
+            });
+    }
+
+    /// Restores the saved game and enters play, or reports why it could not.
+    fn load_game(&mut self) {
+        match save::read_save() {
+            Ok(state) => {
+                state.restore(self);
+                self.saved_turn = Some(self.turn);
+                self.last_event_result = Some("Game loaded.".to_string());
+                self.menu_message = None;
+                self.game_state = GameState::Playing;
+            }
+            Err(e) => {
+                log::error!("Failed to load game: {:#}", e);
+                self.menu_message = Some(format!("Could not load game: {:#}", e));
+            }
+        }
+    }
+
+    /// Writes the save file, reporting the outcome in the result line.
+    /// Returns true on success.
+    fn save_game(&mut self) -> bool {
+        let (msg, saved) = match save::write_save(self) {
+            Ok(()) => {
+                self.saved_turn = Some(self.turn);
+                (format!("Game saved to {}.", SAVE_PATH), true)
+            }
+            Err(e) => {
+                log::error!("Failed to save game: {:#}", e);
+                (format!("Could not save game: {:#}", e), false)
+            }
+        };
+        self.last_event_result = Some(msg);
+        saved
+    }
+
+    /// True once the player has resolved events since the last save or load
+    fn has_unsaved_progress(&self) -> bool {
+        self.game_state == GameState::Playing
+            && self.turn > 0
+            && self.saved_turn != Some(self.turn)
+    }
+
+    /// "Save before quitting?" modal shown when closing with unsaved progress
+    fn show_quit_prompt(&mut self, ctx: &egui::Context) {
+        let mut quit = false;
+        Window::new(RichText::new("Save before quitting?").strong())
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+            .collapsible(false)
+            .order(Order::Foreground)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("You have progress that hasn't been saved.");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        // Stay open if the save failed so the error is visible
+                        quit = self.save_game();
+                        self.quit_prompt_open = quit;
+                    }
+                    if ui.button("Quit anyway").clicked() {
+                        quit = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.quit_prompt_open = false;
+                    }
+                });
+            });
+        if quit {
+            self.quit_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    /// Developer window showing how the last generated event was chosen
+    fn show_generation_trace(&mut self, ctx: &egui::Context) {
+        let mut open = self.generation_trace_open;
+        Window::new("Generation Trace")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let Some(trace) = &self.generation_trace else {
+                    ui.label(RichText::new("No event generated yet.").weak());
+                    return;
+                };
+                for (number, attempt) in trace.attempts.iter().enumerate() {
+                    ui.label(RichText::new(format!("Attempt {}", number + 1)).strong());
+                    ui.label(attempt.summary());
+                }
+                ui.separator();
+                match (&self.last_event_source, trace.handcrafted) {
+                    (_, true) => ui.label("Fell back to a handcrafted event."),
+                    (Some(EventSource::Procedural(id)), false) => {
+                        ui.label(format!("Opened procedural situation {}.", id))
+                    }
+                    _ => ui.label("Opened a procedural event."),
+                };
+            });
+        self.generation_trace_open = open;
+    }
+
+    /// Startup prompt for the player's name, pronouns, and starting scenario.
+    /// Skipping keeps the neutral identity defaults.
+    fn show_name_entry(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |_ui| {});
+        Window::new(RichText::new("Who are you?").strong())
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Scenario:");
+                for (index, scenario) in self.scenarios.iter().enumerate() {
+                    ui.radio_value(&mut self.selected_scenario, index, &scenario.name);
+                }
+                if let Some(scenario) = self.scenarios.get(self.selected_scenario) {
+                    ui.label(RichText::new(&scenario.description).italics().weak());
+                }
+                ui.separator();
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut self.name_input);
+                ui.horizontal(|ui| {
+                    ui.label("Pronouns:");
+                    for pronouns in Pronouns::ALL {
+                        ui.radio_value(
+                            &mut self.player_identity.pronouns,
+                            pronouns,
+                            pronouns.as_str(),
+                        );
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Begin").clicked() {
+                        let name = self.name_input.trim();
+                        if !name.is_empty() {
+                            self.player_identity.name = name.to_string();
+                        }
+                        self.apply_scenario();
+                        self.game_state = GameState::Playing;
+                    }
+                    if ui.button("Skip").clicked() {
+                        self.player_identity = PlayerIdentity::default();
+                        self.apply_scenario();
+                        self.game_state = GameState::Playing;
+                    }
+                    if ui.button("Back").clicked() {
+                        self.game_state = GameState::MainMenu;
+                    }
+                });
+            });
+    }
+
+    /// Slides the tier banner in from the top, holds it, then fades it out
+    fn show_tier_banner(&mut self, ctx: &egui::Context) {
+        let Some(banner) = &self.tier_banner else {
+            return;
+        };
+        let elapsed = ctx.input(|i| i.time) - banner.started_at;
+        let total = TIER_BANNER_SLIDE + TIER_BANNER_HOLD + TIER_BANNER_FADE;
+        if elapsed >= total {
+            self.tier_banner = None;
+            return;
+        }
+
+        let slide = (elapsed / TIER_BANNER_SLIDE).min(1.0) as f32;
+        let alpha = if elapsed > TIER_BANNER_SLIDE + TIER_BANNER_HOLD {
+            (1.0 - (elapsed - TIER_BANNER_SLIDE - TIER_BANNER_HOLD) / TIER_BANNER_FADE) as f32
+        } else {
+            1.0
+        };
+        let promoted = banner.new_tier > banner.old_tier;
+        let (color, verb) = if promoted {
+            (Color32::from_rgb(60, 200, 90), "Promoted")
+        } else {
+            (Color32::from_rgb(220, 60, 60), "Demoted")
+        };
+        let text = format!(
+            "{}: Tier {} \u{2192} Tier {}",
+            verb,
+            Self::tier_name(banner.old_tier),
+            Self::tier_name(banner.new_tier)
+        );
+
+        // Ease out so the banner decelerates into place
+        let offset = TIER_BANNER_SLIDE_DISTANCE * (1.0 - (1.0 - slide).powi(3));
+        Area::new(Id::new("tier_banner"))
+            .anchor(
+                Align2::CENTER_TOP,
+                vec2(0.0, offset - TIER_BANNER_SLIDE_DISTANCE + 40.0),
+            )
+            .order(Order::Tooltip)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(color.linear_multiply(0.25 * alpha))
+                    .stroke(egui::Stroke::new(2.0, color.linear_multiply(alpha)))
+                    .corner_radius(8.0)
+                    .inner_margin(egui::Margin::symmetric(24, 12))
+                    .show(ui, |ui| {
+                        ui.label(
+                            RichText::new(text)
+                                .size(24.0)
+                                .strong()
+                                .color(Color32::WHITE.linear_multiply(alpha)),
+                        );
+                    });
+            });
+        ctx.request_repaint();
+    }
+
+    /// Discards an open event the player's tier has drifted out of (e.g. a
+    /// loaded save) and draws a fresh one for the current position, so
+    /// outcomes are never resolved at a scale meant for another tier.
+    pub fn revalidate_current_event(&mut self) {
+        let Some(event) = &self.current_event else {
+            return;
+        };
+        if event.fits_tier(self.player_tier) {
+            return;
+        }
+        log::warn!(
+            "Discarding '{}' (tiers {}-{}): player is now tier {}",
+            event.title,
+            event.min_tier,
+            event.max_tier,
+            self.player_tier
+        );
+        self.current_event = None;
+        self.open_generated_event();
+    }
+
+    /// Generates an event for the player's position and opens it, keeping
+    /// how it was chosen for the developer tools
+    fn open_generated_event(&mut self) {
+        let (result, trace) = generate_event_traced(self, &mut *self.rng.borrow_mut());
+        self.generation_trace = Some(trace);
+        match result {
+            Ok(event) => {
+                if event.procedural_id.is_none() {
+                    self.record_handcrafted(&event.title);
+                }
+                self.last_event_source = Some(event.source());
+                self.current_event = Some(event);
             }
-            keep
+            Err(e) => {
+                log::error!("Event generation failed: {}", e);
+                self.generation_error = Some(e);
+            }
+        }
+    }
+
+    /// Rolls against an option's risk chance on the game RNG: a uniform 1-100
+    /// at or under `risk_chance` fails, so the choice fails `risk_chance`
+    /// percent of the time (never at 0, always at 100). Exactly one draw
+    /// is taken per resolved choice, risky or not, so generation and
+    /// resolution consume the RNG in a fixed order and a seeded run replays
+    /// both the events that appear and how each choice turns out.
+    pub fn roll_failure(&mut self, risk_chance: u8) -> bool {
+        self.rng.get_mut().random_range(1..=100) <= risk_chance
+    }
+
+    /// Remembers a shown result line so procedural events avoid repeating it
+    fn record_recent_result(&mut self, result: &str) {
+        self.recent_results.push_back(result.to_string());
+        while self.recent_results.len() > RECENT_RESULT_WINDOW {
+            self.recent_results.pop_front();
+        }
+    }
+
+    /// True if the player made this choice in this situation within the
+    /// configured cooldown window
+    pub fn choice_on_cooldown(&self, situation_id: &str, archetype: ChoiceType) -> bool {
+        self.choice_cooldowns
+            .get(situation_id)
+            .and_then(|choices| choices.get(&archetype))
+            .is_some_and(|&turn| self.turn < turn + self.config.choice_cooldown_turns)
+    }
+
+    /// Time of day and week of the current turn
+    pub fn time_context(&self) -> TimeContext {
+        TimeContext::for_turn(self.turn)
+    }
+
+    /// True while pacing holds High severity situations back
+    pub fn high_severity_on_cooldown(&self) -> bool {
+        self.last_high_severity_turn
+            .is_some_and(|turn| self.turn < turn + self.config.high_severity_spacing)
+    }
+
+    /// Draft situations only ever generate in debug builds, and only when
+    /// opted in from Settings
+    pub fn drafts_enabled(&self) -> bool {
+        cfg!(debug_assertions) && self.include_drafts
+    }
+
+    fn start_choice_cooldown(&mut self, situation_id: &str, archetype: ChoiceType) {
+        self.choice_cooldowns
+            .entry(situation_id.to_string())
+            .or_default()
+            .insert(archetype, self.turn);
+
+        // Forget cooldowns that have expired
+        let cutoff = self.turn.saturating_sub(self.config.choice_cooldown_turns);
+        self.choice_cooldowns.retain(|_, choices| {
+            choices.retain(|_, &mut turn| turn > cutoff);
+            !choices.is_empty()
         });
     }
+
+    /// Records a procedural choice for later narrative callbacks
+    fn remember_choice(&mut self, archetype: ChoiceType, domain: EventDomain) {
+        self.choice_memory.push_back(ChoiceMemory {
+            archetype,
+            domain,
+            turn: self.turn,
+        });
+        while self.choice_memory.len() > CHOICE_MEMORY_SIZE {
+            self.choice_memory.pop_front();
+        }
+    }
+
+    /// Forgets every situation seen so far, so all of them can come up again
+    pub fn clear_encounter_history(&mut self) {
+        self.encounter_map.clear();
+    }
+
+    /// Number of situations currently held back as recently seen
+    pub fn encounter_count(&self) -> usize {
+        self.encounter_map.len()
+    }
+
+    /// Remembers when a handcrafted event was shown, so it comes up less
+    /// often for the next `handcrafted_recency_window` turns
+    pub(crate) fn record_handcrafted(&mut self, title: &str) {
+        self.handcrafted_seen.insert(title.to_string(), self.turn);
+        let window = self.config.handcrafted_recency_window;
+        let turn = self.turn;
+        self.handcrafted_seen.retain(|_, seen| turn.saturating_sub(*seen) < window);
+    }
+
+    /// Selection weight of a handcrafted event: 1 once it is outside the
+    /// recency window, less the more recently it was shown
+    pub fn handcrafted_weight(&self, title: &str) -> f64 {
+        let window = self.config.handcrafted_recency_window;
+        let age = self.handcrafted_seen.get(title).map(|&seen| self.turn.saturating_sub(seen));
+        match age {
+            Some(age) if age < window => (age + 1) as f64 / (window + 1) as f64,
+            _ => 1.0,
+        }
+    }
+
+    /// Updates context tracking after an event is resolved
+    pub fn update_event_context(&mut self, domain: EventDomain, situation_id: String) {
+        // Update domain history
+        self.record_recent_domain(domain);
+
+        // Update encounter tracking
+        if self
+            .situation_library
+            .situation_by_id(&situation_id)
+            .is_some_and(|situation| situation.severity == Severity::High)
+        {
+            self.last_high_severity_turn = Some(self.turn);
+        }
+        self.encounter_map.insert(situation_id, self.turn);
+
+        // Clean old encounters
+        let cutoff = self.turn.saturating_sub(ENCOUNTER_COOLDOWN_TURNS);
+        self.encounter_map.retain(|_, &mut turn| turn >= cutoff);
+    }
+}
+
+// --- Turn Actions ---
+// Shared by the egui front end and the headless `Engine`.
+impl LotusApp {
+    /// Steps one petal around the current tier, ageing the player on passing
+    /// the start, and opens the event (or review) for the petal landed on
+    pub(crate) fn move_player(&mut self, clockwise: bool) {
+        let old_petal = self.player_petal;
+        if clockwise {
+            self.player_petal = (self.player_petal + 1) % self.num_petals_per_tier;
+            if self.player_petal < old_petal {
+                self.age_up();
+            }
+        } else {
+            self.player_petal =
+                (self.player_petal + self.num_petals_per_tier - 1) % self.num_petals_per_tier;
+            if self.player_petal > old_petal {
+                self.age_up();
+            }
+        }
+
+        if !self.is_review_petal(self.player_petal) {
+            self.open_generated_event();
+            self.last_event_result = None;
+        } else {
+            let event = review_event(self, &mut *self.rng.borrow_mut());
+            self.current_event = Some(event);
+            self.scs_since_review = 0;
+            if self.player_petal != 0 {
+                self.last_event_result = None;
+            }
+        }
+    }
+
+    /// Resolves the open event with the option at `index`, spending a favor
+    /// on the risk roll if asked. Returns None, leaving the event open, when
+    /// no event is open or it has no option at `index`.
+    pub(crate) fn resolve_option(&mut self, index: usize, spend_favor: bool) -> Option<Resolution> {
+        if index >= self.current_event.as_ref()?.options.len() {
+            return None;
+        }
+        let mut event = self.current_event.take()?;
+        let option = event.options.swap_remove(index);
+        // The graph starts from the stats the run began with
+        if self.stat_history.is_empty() {
+            self.record_stat_snapshot();
+        }
+        self.turn += 1;
+
+        // Update context tracking if this was a procedural event
+        if let (Some(proc_id), Some(proc_domain)) = (&event.procedural_id, &event.procedural_domain)
+        {
+            // Parse the domain string back to enum
+            match proc_domain.parse::<EventDomain>() {
+                Ok(domain) => {
+                    self.update_event_context(domain, proc_id.clone());
+                    if let Some(archetype) = option.archetype {
+                        self.remember_choice(archetype, domain);
+                        self.start_choice_cooldown(proc_id, archetype);
+                    }
+                }
+                Err(e) => log::warn!("Skipping context tracking for '{}': {}", proc_id, e),
+            }
+        }
+        if spend_favor {
+            self.favors = self.favors.saturating_sub(1);
+        }
+        let risky = option.risk_chance > 0;
+        // Roll before checking the favor so the draw is always taken
+        let failed = self.roll_failure(option.risk_chance) && !spend_favor;
+        let succeeded = risky.then_some(!failed);
+        self.run_stats
+            .record_choice(option.archetype, event.domain(), succeeded);
+        let outcome_to_apply = if failed {
+            option
+                .failure_outcome
+                .map(|outcome| (outcome, option.failure_result))
+        } else {
+            Some((option.success_outcome, option.success_result))
+        };
+
+        let mut applied = None;
+        if let Some((outcome, result)) = outcome_to_apply {
+            let (scs_before, finances_before) = (self.social_credit_score, self.finances);
+            let choice = ResolvedChoice {
+                event_title: &event.title,
+                archetype: option.archetype,
+                succeeded,
+                result: &result,
+            };
+            self.apply_outcome(std::slice::from_ref(&outcome), &choice);
+            self.run_stats.record_outcome(
+                self.social_credit_score - scs_before,
+                self.finances - finances_before,
+            );
+            self.record_recent_result(&result);
+            self.last_event_result = Some(result.clone());
+            applied = Some((result, outcome));
+        }
+        self.record_stat_snapshot();
+        Some(Resolution { succeeded, applied })
+    }
+
+    /// Re-derives the tier from SCS on a review petal. Returns the old tier
+    /// when it changed, after logging the change.
+    pub(crate) fn apply_tier_review(&mut self) -> Option<usize> {
+        let old_tier = self.player_tier;
+        if self.offer_grace() {
+            return None;
+        }
+        if !self.update_player_tier_from_scs() {
+            return None;
+        }
+        self.history.push(format!(
+            "Tier changed from {} to {}!",
+            Self::tier_name(old_tier),
+            Self::tier_name(self.player_tier)
+        ));
+        Some(old_tier)
+    }
+
+    /// Holds back the player's first drop into Tier D and opens the warning
+    /// event in its place. Returns true when the warning was opened.
+    fn offer_grace(&mut self) -> bool {
+        if !self.config.tier_d_grace
+            || self.used_grace
+            || self.player_tier == 0
+            || self.social_credit_score > TIER_D_MAX
+            || self.current_event.is_some()
+        {
+            return false;
+        }
+        self.used_grace = true;
+        let scs_needed = TIER_D_MAX + 1 - self.social_credit_score;
+        self.current_event = Some(grace_event(self, scs_needed));
+        self.history
+            .push("Final warning: your score is about to fall to Tier D.".to_string());
+        true
+    }
+
+    /// Opens the bankruptcy event once finances have hit the floor and no
+    /// other event is open
+    pub(crate) fn queue_bankruptcy_event(&mut self) {
+        if self.bankruptcy_pending && self.current_event.is_none() {
+            self.bankruptcy_pending = false;
+            self.current_event = Some(bankruptcy_event(self));
+        }
+    }
 }
 
 impl eframe::App for LotusApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // --- Quit Interception ---
+        // Both the Exit button and the OS close button land here
+        if ctx.input(|i| i.viewport().close_requested())
+            && !self.quit_confirmed
+            && self.has_unsaved_progress()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.quit_prompt_open = true;
+        }
+
+        // --- Front End ---
+        match self.game_state {
+            GameState::MainMenu => return self.show_main_menu(ctx),
+            GameState::NewGame => return self.show_name_entry(ctx),
+            GameState::Settings => return self.show_settings(ctx),
+            GameState::Summary => return self.show_run_summary(ctx),
+            GameState::Playing => {}
+        }
+
         let event_is_open = self.current_event.is_some();
 
         // --- Left Stats Panel ---
@@ -213,14 +1194,32 @@ impl eframe::App for LotusApp {
                         ui.heading("Player Status");
                     });
                     ui.separator();
+                    ui.label(format!("Name: {}", self.player_identity.name));
                     ui.label(format!("Age: {}", self.player_age));
                     ui.label(format!("Life Stage: {}", self.life_stage));
+                    ui.label(format!("Time: {}", self.time_context().describe()));
                     ui.label(
                         RichText::new(format!("Social Credit: {}", self.social_credit_score))
                             .strong(),
                     );
-                    ui.label(format!("Finances (¥): {}", self.finances));
+                    let cap = self.config.finance_cap;
+                    let exact = if cap > 0 {
+                        format!(
+                            "{} ¥ of {} ¥",
+                            thousands(self.finances.into()),
+                            thousands(cap.into())
+                        )
+                    } else {
+                        format!("{} ¥", thousands(self.finances.into()))
+                    };
+                    ui.label(format!("Finances: {} ¥", amount(self.finances.into())))
+                        .on_hover_text(exact);
+                    if self.legacy > 0 {
+                        ui.label(format!("Legacy: {} ¥", amount(self.legacy_amount())))
+                            .on_hover_text("Finances banked above the cap, passed down to heirs");
+                    }
                     ui.label(format!("Career: Lvl {}", self.career_level));
+                    ui.label(format!("Favors: {}", self.favors));
                 });
                 ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Default); // Ensure default cursor
             });
@@ -235,11 +1234,42 @@ impl eframe::App for LotusApp {
                         ui.heading("Guanxi Network");
                     });
                     ui.separator();
-                    ui.label(format!("Family: {}", self.guanxi_family));
-                    ui.label(format!("Network: {}", self.guanxi_network));
-                    ui.label(format!("Party: {}", self.guanxi_party));
+                    let caps = self.config.guanxi_caps;
+                    for (label, value, cap) in [
+                        ("Family", self.guanxi_family, caps.family),
+                        ("Network", self.guanxi_network, caps.network),
+                        ("Party", self.guanxi_party, caps.party),
+                    ] {
+                        if cap > 0 {
+                            ui.label(format!("{}: {}/{}", label, value, cap));
+                        } else {
+                            ui.label(format!("{}: {}", label, value));
+                        }
+                    }
+                });
+            });
+
+        // --- Status Bar ---
+        if self.show_status_bar {
+            egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.small(format!("Turn: {}", self.turn));
+                    ui.separator();
+                    ui.small(format!("Seed: {}", self.rng.borrow().describe()));
+                    if self.debug_tools {
+                        ui.separator();
+                        ui.small(format!("Encounters: {}", self.encounter_count()));
+                    }
+                    ui.separator();
+                    let source = match &self.last_event_source {
+                        Some(EventSource::Procedural(id)) => format!("procedural ({})", id),
+                        Some(EventSource::Handcrafted) => "handcrafted".to_string(),
+                        None => "none yet".to_string(),
+                    };
+                    ui.small(format!("Last event: {}", source));
                 });
             });
+        }
 
         // --- Bottom History Panel ---
         egui::TopBottomPanel::bottom("bottom_panel")
@@ -262,54 +1292,66 @@ impl eframe::App for LotusApp {
                     if ui.button("Exit Application").clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                     }
-                    let old_petal = self.player_petal;
-                    let mut moved = false;
+                    if ui.button("Screenshot Board").clicked() {
+                        self.export_board_snapshot();
+                    }
+                    if ui.button("Save Game").clicked() {
+                        self.save_game();
+                    }
+                    if self.stats_dashboard && ui.button("Stats").clicked() {
+                        self.stats_open = !self.stats_open;
+                    }
+                    if ui.button("Graph").clicked() {
+                        self.stat_graph_open = !self.stat_graph_open;
+                    }
+                    if self.debug_tools && ui.button("Content Browser").clicked() {
+                        self.content_browser_open = !self.content_browser_open;
+                    }
+                    if self.debug_tools && ui.button("Trace").clicked() {
+                        self.generation_trace_open = !self.generation_trace_open;
+                    }
+                    if self.debug_tools
+                        && self.log_handle.is_some()
+                        && ui.button("Log").clicked()
+                    {
+                        self.log_viewer_open = !self.log_viewer_open;
+                    }
+                    if self.debug_tools && ui.button("Clear Encounters").clicked() {
+                        self.clear_encounter_history();
+                    }
+                    if self.board_view != BoardView::default()
+                        && ui.button("Reset View").clicked()
+                    {
+                        self.board_view = BoardView::default();
+                    }
                     if ui.button("Move Counter-Clockwise").clicked() {
-                        self.player_petal = (self.player_petal + self.num_petals_per_tier - 1)
-                            % self.num_petals_per_tier;
-                        moved = true;
-                        if self.player_petal > old_petal {
-                            self.age_up();
-                        }
+                        self.move_player(false);
                     }
                     if ui.button("Move Clockwise").clicked() {
-                        self.player_petal = (self.player_petal + 1) % self.num_petals_per_tier;
-                        moved = true;
-                        if self.player_petal < old_petal {
-                            self.age_up();
-                        }
-                    }
-                    if moved {
-                        if !self.is_review_petal(self.player_petal) {
-                            self.current_event = Some(generate_event(self));
-                            self.last_event_result = None;
-                        } else {
-                            self.current_event = None;
-                            if self.player_petal != 0 {
-                                self.last_event_result = None;
-                            }
-                        }
+                        self.move_player(true);
                     }
                 });
             });
 
             // --- Last Event Result & Status ---
             if !event_is_open {
-                if let Some(result_text) = &self.last_event_result {
-                    if !result_text.is_empty() {
-                        ui.label(
-                            RichText::new(result_text)
-                                .color(Color32::from_rgb(200, 200, 100))
-                                .strong(),
-                        );
-                    }
+                if let Some(result_text) = &self.last_event_result
+                    && !result_text.is_empty()
+                {
+                    ui.label(
+                        RichText::new(result_text)
+                            .color(Color32::from_rgb(200, 200, 100))
+                            .strong(),
+                    );
                 }
                 if self.is_review_petal(self.player_petal) {
                     ui.label(RichText::new("SCS Review...").strong());
-                    if self.update_player_tier_from_scs() {
-                        let tier_msg = format!("Tier changed to {}!", self.player_tier);
-                        self.history.push(tier_msg.clone());
-                        ui.label(RichText::new(tier_msg).color(Color32::RED).strong());
+                    if let Some(old_tier) = self.apply_tier_review() {
+                        self.tier_banner = Some(TierBanner {
+                            old_tier,
+                            new_tier: self.player_tier,
+                            started_at: ctx.input(|i| i.time),
+                        });
                     } else {
                         ui.label("Tier remains unchanged.");
                     }
@@ -318,20 +1360,48 @@ impl eframe::App for LotusApp {
 
             // --- Game Board Widget ---
             ui.centered_and_justified(|ui| {
-                let player_total_index =
-                    self.player_tier * self.num_petals_per_tier + self.player_petal;
-                ui.add(LotusWidget::new(
-                    self.num_tiers,
-                    self.num_petals_per_tier,
-                    player_total_index,
-                ));
+                ui.add(
+                    LotusWidget::new(
+                        TIER_COUNT,
+                        self.num_petals_per_tier,
+                        self.player_total_index(),
+                        &self.board_theme,
+                        self.config.widget_style,
+                    )
+                    .still(self.still_board)
+                    .view(&mut self.board_view),
+                );
             });
         });
 
         // --- Event Modal Logic ---
-        let mut outcome_to_apply = None;
-        let mut close_event = false;
+        self.revalidate_current_event();
+
+        // Render from a borrow and only record the clicked index; the event is
+        // taken out of `current_event` once after rendering, so nothing is cloned.
+        let window_width = self.event_window_width(ctx);
+        let mut chosen_option = None;
+        let mut spend_favor = false;
         if let Some(event) = self.current_event.as_ref() {
+            // Number keys pick an option, unless another prompt is on top. A
+            // single-option event is a narrative beat, acknowledged with Enter.
+            // Escape acknowledges or does nothing, as the config allows.
+            let single_option = event.options.len() == 1;
+            let escape_option = event.escape_option(&self.config);
+            if !self.quit_prompt_open {
+                chosen_option = ctx
+                    .input(|i| {
+                        if single_option && i.key_pressed(Key::Enter) {
+                            return Some(0);
+                        }
+                        if i.key_pressed(Key::Escape) {
+                            return escape_option;
+                        }
+                        OPTION_KEYS.iter().position(|&key| i.key_pressed(key))
+                    })
+                    .filter(|&index| index < event.options.len());
+            }
+
             // Darkened overlay
             Area::new(Id::new("event_overlay"))
                 .fixed_pos(ctx.content_rect().min)
@@ -345,18 +1415,93 @@ impl eframe::App for LotusApp {
                 });
 
             // Event Window
-            Window::new(RichText::new(&event.title).strong())
+            let procedural_id = event
+                .procedural_id
+                .as_deref()
+                .filter(|_| self.show_event_source);
+            let title = match procedural_id {
+                Some(_) => format!("\u{2699} {}", event.title),
+                None => event.title.clone(),
+            };
+            let mut title = RichText::new(title).strong();
+            let mut frame = egui::Frame::window(&ctx.style());
+            if let Some(accent) = event
+                .domain()
+                .and_then(|domain| self.board_theme.domain_accent(domain))
+            {
+                title = title.color(accent);
+                frame = frame.stroke(egui::Stroke::new(2.0, accent));
+            }
+            // A missing or broken image shows the theme's unknown glyph instead
+            let icon = event
+                .icon
+                .as_deref()
+                .map(|name| self.icons.texture(ctx, name));
+            Window::new(title)
+                .frame(frame)
                 .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
                 .collapsible(false)
                 .order(Order::Foreground)
                 .resizable(false)
                 .show(ctx, |ui| {
-                    ui.set_max_width(350.0);
-                    ui.add(egui::Label::new(&event.description).wrap());
+                    ui.set_width(window_width);
+                    if let Some(icon) = &icon {
+                        ui.vertical_centered(|ui| match icon {
+                            Some(texture) => {
+                                ui.add(
+                                    egui::Image::new(texture)
+                                        .fit_to_exact_size(vec2(ICON_SIZE, ICON_SIZE)),
+                                );
+                            }
+                            None => {
+                                ui.label(
+                                    RichText::new(&self.board_theme.unknown_glyph)
+                                        .size(ICON_SIZE * 0.75),
+                                );
+                            }
+                        });
+                    }
+                    ui.add(egui::Label::new(event.summary()).wrap());
+                    if let Some(details) = event.details() {
+                        // Keyed by turn so every new event starts collapsed
+                        egui::CollapsingHeader::new("Tell me more")
+                            .id_salt(("event_details", self.turn))
+                            .default_open(false)
+                            .show(ui, |ui| {
+                                ui.add(egui::Label::new(details).wrap());
+                                if self.debug_tools {
+                                    ui.separator();
+                                    for part in &event.description_parts {
+                                        ui.label(
+                                            RichText::new(format!("[{}] {}", part.role, part.text))
+                                                .small()
+                                                .weak(),
+                                        );
+                                    }
+                                }
+                            });
+                    }
                     ui.separator();
+                    if single_option {
+                        let keys = match escape_option {
+                            Some(_) => "Enter or Esc",
+                            None => "Enter",
+                        };
+                        let hint = format!("There is only one way forward ({}).", keys);
+                        ui.label(RichText::new(hint).weak());
+                    }
                     ui.vertical_centered_justified(|ui| {
-                        for option in &event.options {
-                            let button_response = ui.button(&option.text);
+                        for (index, option) in event.options.iter().enumerate() {
+                            // Options the player can't take are filtered out when the event is
+                            // built, so every shown option gets a number
+                            let mut label = match OPTION_KEYS.get(index) {
+                                Some(_) => format!("{}. {}", index + 1, option.text),
+                                None => option.text.clone(),
+                            };
+                            if !single_option && escape_option == Some(index) {
+                                label.push_str(" (Esc)");
+                            }
+                            let button_response = ui.add(egui::Button::new(label).wrap());
 
                             // --- Predictive Tooltip ---
                             button_response.clone().on_hover_ui(|ui| {
@@ -372,55 +1517,108 @@ impl eframe::App for LotusApp {
                                     "None"
                                 };
                                 ui.label(format!("Risk: {} ({}%)", risk_text, option.risk_chance));
-                            });
+                                if let Some(breakdown) = &option.risk_breakdown {
+                                    ui.small(RichText::new(breakdown.describe()).weak());
+                                }
 
-                            if button_response.clicked() {
-                                let mut rng = rand::rng();
-                                if option.risk_chance > 0
-                                    && rng.random_range(1..=100) <= option.risk_chance
-                                {
-                                    if let Some(outcome) = &option.failure_outcome {
-                                        outcome_to_apply =
-                                            Some((outcome.clone(), option.failure_result.clone()));
+                                let (success, failure, risk) = self.preview_outcome(option);
+                                ui.label(format!("Success: {}", Self::format_stat_deltas(&success)));
+                                match failure {
+                                    Some(failure) => {
+                                        ui.label(format!("Failure: {}", Self::format_stat_deltas(&failure)));
                                     }
-                                } else {
-                                    outcome_to_apply = Some((
-                                        option.success_outcome.clone(),
-                                        option.success_result.clone(),
-                                    ));
+                                    None if risk > 0 => {
+                                        ui.label("Failure: No change");
+                                    }
+                                    None => {}
                                 }
-                                close_event = true;
+                            });
+
+                            if button_response.clicked() {
+                                chosen_option = Some(index);
+                            }
+                            if option.risk_chance > 0
+                                && self.favors > 0
+                                && ui
+                                    .small_button(format!("Call in a favor ({} left)", self.favors))
+                                    .on_hover_text("Guarantee success, skipping the risk roll")
+                                    .clicked()
+                            {
+                                chosen_option = Some(index);
+                                spend_favor = true;
                             }
                         }
                     });
+                    if let Some(situation_id) = procedural_id {
+                        ui.separator();
+                        ui.small(RichText::new(format!("Procedural: {}", situation_id)).weak());
+                    }
                 });
         }
 
-        if close_event {
-            // Update context tracking if this was a procedural event
-            if let Some(event) = &self.current_event {
-                if let (Some(proc_id), Some(proc_domain)) =
-                    (&event.procedural_id, &event.procedural_domain)
-                {
-                    // Parse the domain string back to enum
-                    let domain = match proc_domain.as_str() {
-                        "Family" => EventDomain::Family,
-                        "Work" => EventDomain::Work,
-                        "Public" => EventDomain::Public,
-                        "Party" => EventDomain::Party,
-                        _ => EventDomain::Public, // fallback
-                    };
-                    self.update_event_context(domain, proc_id.clone());
-                }
-            }
-            self.current_event = None;
+        if self.stats_dashboard && self.stats_open {
+            self.show_stats_dashboard(ctx);
+        }
+        if self.stat_graph_open {
+            self.show_stat_graph(ctx);
         }
 
-        if let Some((outcome, result)) = outcome_to_apply {
-            self.apply_outcome(&outcome, left_panel_response.response.rect, &result);
-            self.last_event_result = Some(result);
+        // --- Developer Windows ---
+        if self.debug_tools && self.content_browser_open {
+            self.show_content_browser(ctx);
+        }
+        if self.debug_tools && self.generation_trace_open {
+            self.show_generation_trace(ctx);
+        }
+        if self.debug_tools && self.log_viewer_open {
+            self.show_log_viewer(ctx);
+        }
+
+        // --- Quit Prompt ---
+        if self.quit_prompt_open {
+            self.show_quit_prompt(ctx);
         }
 
+        // --- Generation Error Modal ---
+        let mut dismiss_error = false;
+        if let Some(error) = &self.generation_error {
+            Window::new(RichText::new("No Event Available").strong())
+                .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+                .collapsible(false)
+                .order(Order::Foreground)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.set_width(window_width);
+                    ui.add(
+                        egui::Label::new(format!(
+                            "Nothing happens this turn: {}. Please check the event data.",
+                            error
+                        ))
+                        .wrap(),
+                    );
+                    ui.separator();
+                    ui.vertical_centered_justified(|ui| {
+                        dismiss_error = ui.button("Continue").clicked();
+                    });
+                });
+        }
+        if dismiss_error {
+            self.generation_error = None;
+        }
+
+        if let Some(index) = chosen_option
+            && let Some(resolution) = self.resolve_option(index, spend_favor)
+            && let Some((_, outcome)) = &resolution.applied
+        {
+            self.add_outcome_floaters(outcome, left_panel_response.response.rect);
+        }
+
+        // Hitting the finance floor interrupts play with the bankruptcy event
+        self.queue_bankruptcy_event();
+
+        // --- Tier Change Banner ---
+        self.show_tier_banner(ctx);
+
         // --- Floating Text System ---
         let delta_time = ctx.input(|i| i.stable_dt);
         self.floating_texts.retain_mut(|ft| {
@@ -447,3 +1645,331 @@ impl eframe::App for LotusApp {
             });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CarryOver, GameConfig, GuanxiCaps};
+    use crate::fixture::{Fixture, TEST_SEED};
+    use crate::game_data::generate_event;
+    use crate::game_rng::GameRng;
+    use crate::scenario::{StartingStat, load_scenarios};
+    use anyhow::Result;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    #[test]
+    fn scs_bands_split_at_tier_boundaries() {
+        for (scs, tier) in [
+            (i32::MIN, 0),
+            (199, 0),
+            (200, 1),
+            (399, 1),
+            (400, 2),
+            (749, 2),
+            (750, 3),
+            (999, 3),
+            (1000, 4),
+            (i32::MAX, TIER_COUNT - 1),
+        ] {
+            assert_eq!(tier_for_scs(scs), tier, "SCS {}", scs);
+        }
+    }
+
+    /// Never at 0%, always at 100%, and within four standard deviations in
+    /// between, which at low chances is tighter than a one-point off-by-one.
+    /// Chances are interleaved so each sees draws from the whole stream.
+    #[test]
+    fn risk_rolls_fail_at_the_risk_chance() -> Result<()> {
+        const CHANCES: [u8; 8] = [0, 1, 10, 25, 50, 75, 95, 100];
+        const ROLLS: usize = 20_000;
+        let mut app = Fixture::new()?.build()?;
+        app.rng = RefCell::new(GameRng::seeded(TEST_SEED));
+        let mut failed = [0usize; CHANCES.len()];
+        for _ in 0..ROLLS {
+            for (count, &chance) in failed.iter_mut().zip(&CHANCES) {
+                if app.roll_failure(chance) {
+                    *count += 1;
+                }
+            }
+        }
+        for (&count, &chance) in failed.iter().zip(&CHANCES) {
+            let rate = count as f64 / ROLLS as f64;
+            let expected = f64::from(chance) / 100.0;
+            let deviation = (expected * (1.0 - expected) / ROLLS as f64).sqrt();
+            match chance {
+                0 => assert_eq!(count, 0),
+                100 => assert_eq!(count, ROLLS),
+                _ => assert!((rate - expected).abs() <= 4.0 * deviation, "{}%: {}", chance, rate),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_option_index_leaves_the_event_open() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.current_event = Some(generate_event(&app)?);
+        let options = app.current_event.as_ref().map_or(0, |event| event.options.len());
+        assert!(app.resolve_option(options, false).is_none());
+        assert!(app.current_event.is_some());
+        assert_eq!(app.turn, 0);
+        Ok(())
+    }
+
+    /// A Tier C player one review away from Tier D
+    fn facing_tier_d(grace: bool) -> Result<LotusApp> {
+        let mut config = GameConfig::from_embedded()?;
+        config.rng_seed = Some(TEST_SEED);
+        config.tier_d_grace = grace;
+        let mut app = Fixture { config, ..Fixture::new()? }.build()?;
+        app.apply_scenario();
+        app.player_tier = 1;
+        app.social_credit_score = 150;
+        app.guanxi_family = 2;
+        app.current_event = None;
+        Ok(app)
+    }
+
+    fn choose(app: &mut LotusApp, prefix: &str) {
+        let index = app
+            .current_event
+            .as_ref()
+            .and_then(|event| event.options.iter().position(|o| o.text.starts_with(prefix)))
+            .unwrap_or_else(|| panic!("no '{}' option", prefix));
+        app.resolve_option(index, false);
+    }
+
+    #[test]
+    fn first_drop_to_tier_d_opens_the_warning_once() -> Result<()> {
+        let mut app = facing_tier_d(true)?;
+        assert_eq!(app.apply_tier_review(), None);
+        assert_eq!(app.player_tier, 1);
+        assert!(app.current_event.as_ref().is_some_and(|e| e.title == "Final Warning"));
+        assert!(app.used_grace);
+
+        choose(&mut app, "Have your family vouch");
+        assert_eq!(app.apply_tier_review(), None, "SCS {}", app.social_credit_score);
+        assert_eq!(app.player_tier, 1);
+
+        // A second drop isn't held back
+        app.social_credit_score = 150;
+        assert_eq!(app.apply_tier_review(), Some(1));
+        assert!(app.current_event.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn accepting_the_warning_drops_to_tier_d() -> Result<()> {
+        let mut app = facing_tier_d(true)?;
+        app.apply_tier_review();
+        choose(&mut app, "Accept the downgrade");
+        assert_eq!(app.apply_tier_review(), Some(1));
+        assert_eq!(app.player_tier, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn without_the_grace_the_drop_is_immediate() -> Result<()> {
+        let mut app = facing_tier_d(false)?;
+        assert_eq!(app.apply_tier_review(), Some(1));
+        assert!(app.current_event.is_none());
+        Ok(())
+    }
+
+    fn guanxi_gain(family: i32, network: i32, party: i32) -> EventOutcome {
+        EventOutcome {
+            guanxi_family_change: family,
+            guanxi_network_change: network,
+            guanxi_party_change: party,
+            ..EventOutcome::default()
+        }
+    }
+
+    /// Guanxi after applying `outcome` from `start`, and the SCS it paid
+    fn apply_guanxi(app: &mut LotusApp, start: [u32; 3], outcome: EventOutcome) -> ([u32; 3], i32) {
+        (app.guanxi_family, app.guanxi_network, app.guanxi_party) = (start[0], start[1], start[2]);
+        let scs = app.social_credit_score;
+        app.apply_guanxi_changes(&outcome);
+        (
+            [app.guanxi_family, app.guanxi_network, app.guanxi_party],
+            app.social_credit_score - scs,
+        )
+    }
+
+    #[test]
+    fn guanxi_stops_at_its_cap() -> Result<()> {
+        let mut config = GameConfig::from_embedded()?;
+        config.guanxi_caps = GuanxiCaps {
+            family: 10,
+            network: 10,
+            party: 0,
+            overflow: GuanxiOverflow::Scs,
+            scs_per_point: 2,
+        };
+        let mut app = Fixture { config, ..Fixture::new()? }.build()?;
+        // Held at 10, with 3 + 1 points over at 2 SCS each
+        assert_eq!(apply_guanxi(&mut app, [8, 9, 0], guanxi_gain(5, 2, 0)), ([10, 10, 0], 8));
+        // Losses and uncapped stats apply in full
+        assert_eq!(apply_guanxi(&mut app, [10, 10, 5], guanxi_gain(-3, -1, -5)), ([7, 9, 0], 0));
+        assert_eq!(apply_guanxi(&mut app, [0, 0, 40], guanxi_gain(0, 0, 100)), ([0, 0, 140], 0));
+        // A stat already over its cap stays there
+        assert_eq!(apply_guanxi(&mut app, [12, 3, 0], guanxi_gain(2, 1, 0)), ([12, 4, 0], 4));
+
+        app.config.guanxi_caps.overflow = GuanxiOverflow::Clamp;
+        assert_eq!(apply_guanxi(&mut app, [8, 9, 0], guanxi_gain(5, 2, 0)), ([10, 10, 0], 0));
+        Ok(())
+    }
+
+    #[test]
+    fn finances_over_the_cap_move_into_legacy() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        let cap = app.config.finance_cap;
+        assert!(cap > 0, "the embedded config turns the cap off");
+        app.finances = cap + 2500;
+        app.bank_finance_overflow();
+        app.finances = cap + 500;
+        app.bank_finance_overflow();
+        assert_eq!((app.finances, app.legacy), (cap, 3000));
+
+        app.config.finance_cap = 0;
+        app.finances = cap + 2500;
+        app.bank_finance_overflow();
+        assert_eq!((app.finances, app.legacy), (cap + 2500, 3000));
+        Ok(())
+    }
+
+    /// New Game+ restarts from the scenario and keeps exactly what the
+    /// carry-over selects
+    #[test]
+    fn new_game_plus_keeps_the_carry_over() -> Result<()> {
+        let scenarios = load_scenarios()?;
+        let start_finances = scenarios
+            .first()
+            .and_then(|s| s.finances.fixed())
+            .expect("the first scenario must start with fixed finances");
+        for carry_all in [true, false] {
+            let mut app = Fixture::new()?.build()?;
+            let starting_favors = app.config.starting_favors;
+            app.turn = 40;
+            app.finances = 2000;
+            app.favors = starting_favors + 3;
+            app.legacy = 7000;
+            app.update_event_context(EventDomain::Work, "work_bribery_opportunity".to_string());
+            app.carry_over = CarryOver {
+                finance_fraction: if carry_all { 0.5 } else { 0.0 },
+                encounter_history: carry_all,
+                favors: carry_all,
+                legacy: carry_all,
+            };
+            app.new_game_plus();
+
+            let kept = u32::from(carry_all);
+            assert_eq!(app.turn, 0);
+            assert_eq!(app.game_state, GameState::Playing);
+            assert_eq!(app.finances, start_finances + 1000 * kept as i32);
+            assert_eq!(app.favors, starting_favors + 3 * kept);
+            assert_eq!(app.legacy, 7000 * u64::from(kept));
+            assert_eq!(app.encounter_count(), kept as usize);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn new_game_plus_carries_at_most_the_cap() -> Result<()> {
+        let start_finances = load_scenarios()?
+            .first()
+            .and_then(|s| s.finances.fixed())
+            .expect("the first scenario must start with fixed finances");
+        let mut app = Fixture::new()?.build()?;
+        app.carry_over.finance_fraction = 1.0;
+        app.config.finance_cap = 0;
+        app.finances = i32::MAX;
+        app.new_game_plus();
+        assert_eq!(app.finances, i32::MAX);
+
+        app.config.finance_cap = 5000;
+        app.finances = 1_000_000;
+        app.legacy = 0;
+        app.carry_over.legacy = true;
+        app.new_game_plus();
+        assert_eq!(app.finances, 5000);
+        // The carried 5000 fills the cap, so the starting finances are banked
+        assert_eq!(app.legacy, start_finances as u64);
+        Ok(())
+    }
+
+    /// An open procedural event is kept while the tier still fits it and
+    /// replaced once the tier drifts out of range
+    #[test]
+    fn tier_drift_regenerates_the_open_event() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        let drift_tier = 0;
+        app.player_tier = TIER_COUNT - 1;
+        app.rng = RefCell::new(GameRng::seeded(TEST_SEED));
+        let event = (0..25)
+            .filter_map(|_| generate_event(&app).ok())
+            .find(|event| event.procedural_id.is_some() && !event.fits_tier(drift_tier))
+            .expect("no high-tier procedural event to drift from");
+
+        app.current_event = Some(event.clone());
+        app.revalidate_current_event();
+        assert_eq!(app.current_event.as_ref().map(|e| &e.title), Some(&event.title));
+
+        app.player_tier = drift_tier;
+        app.revalidate_current_event();
+        match &app.current_event {
+            Some(replacement) => {
+                assert!(replacement.fits_tier(drift_tier), "{}", replacement.title)
+            }
+            None => assert!(app.generation_error.is_some()),
+        }
+        Ok(())
+    }
+
+    /// Ranged starting stats land in their range, replay from the same seed
+    /// and keep the scenario in one starting tier whatever the roll
+    #[test]
+    fn scenario_rolls_replay_within_the_preset() -> Result<()> {
+        const SEEDS: u64 = 20;
+        let in_range = |value: i32, stat: StartingStat<i32>| match stat {
+            StartingStat::Fixed(fixed) => value == fixed,
+            StartingStat::Range { min, max } => (min..=max).contains(&value),
+        };
+        let mut app = Fixture::new()?.build()?;
+        let scenarios = load_scenarios()?;
+        let rolled = scenarios.iter().enumerate().filter(|(_, s)| s.rolls_stats());
+        for (index, scenario) in rolled {
+            let mut tiers = HashSet::new();
+            for seed in 0..SEEDS {
+                let mut roll = || {
+                    app.rng = RefCell::new(GameRng::seeded(seed));
+                    app.selected_scenario = index;
+                    app.apply_scenario();
+                    (
+                        app.social_credit_score,
+                        app.finances,
+                        app.career_level,
+                        app.guanxi_family,
+                        app.guanxi_network,
+                        app.guanxi_party,
+                        app.player_tier,
+                    )
+                };
+                let start = roll();
+                assert_eq!(start, roll(), "'{}' seed {}", scenario.name, seed);
+                assert!(
+                    in_range(start.0, scenario.social_credit_score)
+                        && in_range(start.1, scenario.finances),
+                    "'{}' seed {} rolled {:?}",
+                    scenario.name,
+                    seed,
+                    start
+                );
+                tiers.insert(start.6);
+            }
+            assert_eq!(tiers.len(), 1, "'{}' starts in tiers {:?}", scenario.name, tiers);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+//! Content self-checks, run with `cargo run -- --audit`.
+//!
+//! Each check drives the real pipeline over the shipped content with a seeded
+//! RNG and records every problem it finds, so one run reports all broken
+//! content at once. How the rules behave is covered by the unit tests.
+
+use crate::config::GameConfig;
+use crate::converter;
+use crate::fixture::Fixture;
+use crate::game_data::{generate_event_with_rng, load_event_database};
+use crate::icons::{embedded_icons, load_icon};
+use crate::paths::Paths;
+use crate::procedural::memory::PAST_CHOICE_TOKEN;
+use crate::procedural::text_assembly::{
+    SubstitutionContext, golden_description_mismatches, resolve_placeholders,
+    unresolved_placeholders,
+};
+use crate::procedural::{PlayerIdentity, SituationLibrary};
+use crate::requirements::RequirementKey;
+use crate::TIER_COUNT;
+use anyhow::{Result, bail};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// --- Configuration ---
+const AUDIT_SEED: u64 = 42;
+const DESCRIPTOR_DRAWS: usize = 20;
+// ---------------------
+
+/// Main audit function, called from `main.rs`.
+pub fn run_audit() -> Result<()> {
+    let library = SituationLibrary::from_embedded_configs()?;
+    let mut failures = Vec::new();
+
+    if let Err(e) = library.check_life_stages(GameConfig::from_embedded()?.life_stage_count()) {
+        failures.push(format!("life stages: {}", e));
+    }
+
+    if let Err(e) = library.check_tiers() {
+        failures.push(format!("tiers: {}", e));
+    }
+
+    check_placeholders_resolve(&library, &mut failures);
+    check_colleague_descriptor_tiers(&library, &mut failures);
+    check_duplicate_detection(&library, &mut failures)?;
+    check_requirement_ceiling(&library, &mut failures)?;
+    failures.extend(golden_description_mismatches(&library)?);
+    check_without_handcrafted_events(&library, &mut failures)?;
+    check_content_paths(&library, &mut failures)?;
+    check_scenario_start_petals(&library, &mut failures)?;
+    check_guanxi_caps(&library, &mut failures)?;
+    check_event_icons(&library, &mut failures)?;
+    check_tier_risk_curves(&mut failures)?;
+
+    if failures.is_empty() {
+        println!("Audit passed.");
+        Ok(())
+    } else {
+        for failure in &failures {
+            eprintln!("  {}", failure);
+        }
+        bail!("Audit found {} problem(s)", failures.len())
+    }
+}
+
+/// Every embedded icon must decode and every icon named by content must load.
+fn check_event_icons(library: &SituationLibrary, failures: &mut Vec<String>) -> Result<()> {
+    let named = load_event_database()?
+        .into_iter()
+        .filter_map(|event| event.icon)
+        .chain(library.situations().iter().filter_map(|s| s.icon.clone()));
+    for name in embedded_icons().map(str::to_string).chain(named) {
+        if let Err(e) = load_icon(&name) {
+            failures.push(format!("icons: {:#}", e));
+        }
+    }
+    Ok(())
+}
+
+/// Every description fragment must resolve all of its placeholders at every
+/// tier the situation can appear in, and choice text must resolve too.
+fn check_placeholders_resolve(library: &SituationLibrary, failures: &mut Vec<String>) {
+    let identity = PlayerIdentity::default();
+    for situation in library.situations() {
+        let fragments = &situation.fragments;
+        let all_fragments = fragments
+            .openings
+            .iter()
+            .chain(&fragments.conflicts)
+            .chain(&fragments.stakes)
+            .chain(&fragments.callbacks);
+
+        for fragment in all_fragments {
+            // Callbacks are filled from choice memory before variable substitution
+            let template = fragment.text.replace(PAST_CHOICE_TOKEN, "went along with it");
+            for tier in situation.tier_min..=situation.tier_max.min(TIER_COUNT - 1) {
+                let mut rng = StdRng::seed_from_u64(AUDIT_SEED);
+                let mut context = SubstitutionContext::new(&library.variables, tier, &identity);
+                let text = resolve_placeholders(&template, &mut context, &mut rng);
+                let leftover = unresolved_placeholders(&text);
+                if !leftover.is_empty() {
+                    failures.push(format!(
+                        "{}: unresolved {:?} at tier {} in '{}'",
+                        situation.id, leftover, tier, fragment.text
+                    ));
+                }
+            }
+        }
+
+        for choice in &situation.choices {
+            // Choice text and authored result lines go through the same
+            // substitution pass as descriptions
+            let choice_texts = choice
+                .text_fragments
+                .iter()
+                .chain(&choice.success_text)
+                .chain(&choice.failure_text);
+            for text in choice_texts {
+                for tier in situation.tier_min..=situation.tier_max.min(TIER_COUNT - 1) {
+                    let mut rng = StdRng::seed_from_u64(AUDIT_SEED);
+                    let mut context = SubstitutionContext::new(&library.variables, tier, &identity);
+                    let resolved = resolve_placeholders(text, &mut context, &mut rng);
+                    let leftover = unresolved_placeholders(&resolved);
+                    if !leftover.is_empty() {
+                        failures.push(format!(
+                            "{}: unresolved {:?} at tier {} in {} choice '{}'",
+                            situation.id,
+                            leftover,
+                            tier,
+                            choice.archetype.as_str(),
+                            text
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `{colleague_descriptor}` must draw from the player's tier list, falling
+/// back to tier 2 only when the tier has no list of its own.
+fn check_colleague_descriptor_tiers(library: &SituationLibrary, failures: &mut Vec<String>) {
+    let identity = PlayerIdentity::default();
+    let descriptors = &library.variables.colleague_descriptors;
+    let mut rng = StdRng::seed_from_u64(AUDIT_SEED);
+
+    for tier in 0..TIER_COUNT {
+        let Some(expected) = descriptors
+            .get(&tier.to_string())
+            .or_else(|| descriptors.get("2"))
+        else {
+            failures.push(format!(
+                "colleague_descriptors: no list for tier {} or tier 2",
+                tier
+            ));
+            continue;
+        };
+        for _ in 0..DESCRIPTOR_DRAWS {
+            // A fresh context per draw, or every draw would reuse the first
+            let mut context = SubstitutionContext::new(&library.variables, tier, &identity);
+            let text = resolve_placeholders("{colleague_descriptor}", &mut context, &mut rng);
+            if !expected.contains(&text) {
+                failures.push(format!(
+                    "colleague_descriptors: '{}' is not a tier {} descriptor",
+                    text, tier
+                ));
+            }
+        }
+    }
+}
+
+/// A copied situation must be flagged as a duplicate of its original. Pairs
+/// the shipped library already trips are listed but don't fail the audit.
+fn check_duplicate_detection(library: &SituationLibrary, failures: &mut Vec<String>) -> Result<()> {
+    let threshold = GameConfig::from_embedded()?.duplicate_situation_threshold;
+    for (first, second, similarity) in library.likely_duplicates(threshold) {
+        println!(
+            "  note: '{}' and '{}' share {:.0}% of their text",
+            first,
+            second,
+            similarity * 100.0
+        );
+    }
+
+    let Some(original) = library.situations().first().cloned() else {
+        return Ok(());
+    };
+    let mut situations = library.situations().to_vec();
+    let mut copy = original.clone();
+    copy.id = format!("{}_copy", original.id);
+    situations.push(copy);
+    let copied = SituationLibrary::new(situations, library.variables.clone())?;
+    let flagged = copied
+        .likely_duplicates(threshold.min(1.0))
+        .iter()
+        .any(|(first, second, _)| *first == original.id && second.ends_with("_copy"));
+    if !flagged {
+        failures.push(format!("duplicates: a copy of '{}' was not flagged", original.id));
+    }
+    Ok(())
+}
+
+/// Embedded choices must stay under the requirement ceiling, and a choice
+/// raised far above it must be flagged
+fn check_requirement_ceiling(library: &SituationLibrary, failures: &mut Vec<String>) -> Result<()> {
+    let ceiling = GameConfig::from_embedded()?.requirement_ceiling;
+    for (id, archetype, key, value, limit) in library.unreachable_requirements(&ceiling) {
+        failures.push(format!(
+            "requirements: '{}' {} choice needs {} {} (ceiling {})",
+            id,
+            archetype.as_str(),
+            key,
+            value,
+            limit
+        ));
+    }
+
+    let mut situations = library.situations().to_vec();
+    let Some(choice) = situations.first_mut().and_then(|s| s.choices.first_mut()) else {
+        return Ok(());
+    };
+    choice.requirements.insert(RequirementKey::CareerLevel, 9999);
+    let raised = SituationLibrary::new(situations, library.variables.clone())?;
+    let flagged = raised
+        .unreachable_requirements(&ceiling)
+        .iter()
+        .any(|&(_, _, key, value, _)| key == RequirementKey::CareerLevel && value == 9999);
+    if !flagged {
+        failures.push("requirements: career_level 9999 was not flagged".to_string());
+    }
+    Ok(())
+}
+
+/// The CSVs the game falls back to must convert, and with no handcrafted
+/// events at all every position must still get a procedural event.
+fn check_without_handcrafted_events(
+    library: &SituationLibrary,
+    failures: &mut Vec<String>,
+) -> Result<()> {
+    match converter::events_from_csv(&Paths::default()) {
+        Ok(events) if !events.is_empty() => {}
+        Ok(_) => failures.push("no handcrafted events: the CSVs hold no events".to_string()),
+        Err(e) => failures.push(format!("no handcrafted events: CSV conversion failed: {:#}", e)),
+    }
+
+    let mut app = Fixture { events: Vec::new(), ..Fixture::embedded(library.clone())? }.build()?;
+    let mut rng = StdRng::seed_from_u64(AUDIT_SEED);
+    for life_stage in 1..=app.config.life_stage_count() {
+        for tier in 0..TIER_COUNT {
+            app.life_stage = life_stage;
+            app.player_tier = tier;
+            if let Err(e) = generate_event_with_rng(&app, &mut rng) {
+                failures.push(format!(
+                    "no handcrafted events: stage {} tier {}: {}",
+                    life_stage, tier, e
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The procedural directory in the tree must load the same situations the
+/// binary embeds, so `--procedural-dir data/procedural` plays the same game.
+fn check_content_paths(library: &SituationLibrary, failures: &mut Vec<String>) -> Result<()> {
+    let from_dir = SituationLibrary::from_dir(std::path::Path::new("data/procedural"))?;
+    let ids = |library: &SituationLibrary| -> Vec<String> {
+        library.situations().iter().map(|s| s.id.clone()).collect()
+    };
+    if ids(&from_dir) != ids(library) {
+        failures.push("paths: data/procedural doesn't match the embedded situations".to_string());
+    }
+    Ok(())
+}
+
+
+/// Every scenario must start on a petal that exists and is not a review petal
+fn check_scenario_start_petals(
+    library: &SituationLibrary,
+    failures: &mut Vec<String>,
+) -> Result<()> {
+    let app = Fixture::embedded(library.clone())?.build()?;
+    for scenario in &app.scenarios {
+        if !app.is_valid_start_petal(scenario.start_petal) {
+            failures.push(format!(
+                "scenario '{}': start_petal {} is off the board or a review petal",
+                scenario.name, scenario.start_petal
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// No choice may require more guanxi than the embedded caps allow.
+fn check_guanxi_caps(library: &SituationLibrary, failures: &mut Vec<String>) -> Result<()> {
+    let caps = GameConfig::from_embedded()?.guanxi_caps;
+    let cap_for = |key: RequirementKey| match key {
+        RequirementKey::GuanxiFamily => caps.family,
+        RequirementKey::GuanxiNetwork => caps.network,
+        RequirementKey::GuanxiParty => caps.party,
+        _ => 0,
+    };
+    for situation in library.situations() {
+        for choice in &situation.choices {
+            for (key, value) in choice.requirements.iter() {
+                let cap = cap_for(key);
+                if cap > 0 && value > cap {
+                    failures.push(format!(
+                        "guanxi caps: '{}' requires {} {} above its cap of {}",
+                        situation.id,
+                        value,
+                        key.as_str(),
+                        cap
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Configured tier risk curves must not be empty: an empty curve silently
+/// adds nothing, which is almost certainly a typo.
+fn check_tier_risk_curves(failures: &mut Vec<String>) -> Result<()> {
+    let config = GameConfig::from_embedded()?;
+    for (domain, curve) in &config.tier_risk_curves {
+        if curve.is_empty() {
+            failures.push(format!("tier risk: {} curve is empty", domain.as_str()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,291 @@
+//! Tunable game rules, embedded from `data/game_config.toml`.
+
+use anyhow::{Context, Result, ensure};
+use crate::game_data::MAX_RISK_CHANCE;
+use crate::lotus_widget::WidgetStyle;
+use crate::procedural::EventDomain;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameConfig {
+    /// Fixed seed for every random draw in a game; a fresh seed when unset
+    pub rng_seed: Option<u64>,
+
+    /// Lowest finances can fall; hitting it triggers the bankruptcy event
+    pub finance_floor: i32,
+    /// Highest finances can rise; anything above it becomes legacy. 0 turns
+    /// the cap off.
+    pub finance_cap: i32,
+    /// SCS lost when the player declares bankruptcy
+    pub bankruptcy_scs_penalty: i32,
+    /// The first review that would drop the player to Tier D offers a way
+    /// out instead; off for a harder game
+    pub tier_d_grace: bool,
+    /// Finances paid to buy back SCS on the Tier D warning
+    pub grace_finance_cost: i32,
+
+    // SCS review outcome table
+    pub review_promotion_weight: u32,
+    pub review_neutral_weight: u32,
+    pub review_audit_weight: u32,
+    /// SCS gained (lost) since the last review per extra promotion (audit) weight
+    pub review_scs_per_weight: i32,
+
+    /// Shuffle option order per event; off keeps the authored order
+    pub shuffle_options: bool,
+    /// Append a "Do nothing" option to generated events
+    pub skip_option_enabled: bool,
+    /// SCS lost for choosing to do nothing
+    pub skip_option_scs_penalty: i32,
+    /// Escape acknowledges an event with a single option
+    pub escape_acknowledges: bool,
+    /// Escape picks "Do nothing" on events that offer it
+    pub escape_skips: bool,
+    /// Turns before the same choice in the same situation is offered again
+    pub choice_cooldown_turns: u64,
+    /// Events that must come between two High severity situations
+    pub high_severity_spacing: u64,
+    /// Risk added to Manipulate choices on evenings and weekends
+    pub after_hours_manipulate_risk: i8,
+    /// Procedural generation attempts per event before handcrafted fallback
+    pub procedural_attempts: u32,
+    /// Turns over which a handcrafted event shown again is drawn less often
+    pub handcrafted_recency_window: u64,
+    /// Authored choices a procedural event must offer the player; the last
+    /// attempt settles for one
+    pub min_options: usize,
+
+    /// Favors the player starts with; each one skips a risk roll
+    pub starting_favors: u32,
+    /// Risk added at each player tier (innermost D first) per situation
+    /// domain. Tiers past the end use the last entry; missing domains add nothing.
+    pub tier_risk_curves: HashMap<EventDomain, Vec<i8>>,
+    /// Lowest and highest risk per situation domain; missing domains use 0-95
+    pub risk_clamps: HashMap<EventDomain, RiskClamp>,
+
+    /// Ages at which life stages 2, 3, ... begin, ascending. Stage 1 starts
+    /// at birth, so there is one more stage than there are entries.
+    pub life_stage_ages: Vec<u32>,
+    /// Age at which the run ends with a summary screen
+    pub retirement_age: u32,
+    /// Default carry-over for a New Game+, adjustable on the summary screen
+    pub new_game_plus: CarryOver,
+    /// Soft caps on guanxi gains from outcomes
+    pub guanxi_caps: GuanxiCaps,
+
+    // Event window layout
+    /// Event window width as a fraction of the screen width
+    pub event_window_width_fraction: f32,
+    pub event_window_min_width: f32,
+    pub event_window_max_width: f32,
+    /// Board animation timing; zero durations make frames deterministic
+    pub widget_style: WidgetStyle,
+
+    /// Log records kept for the developer Log window
+    pub log_buffer_lines: usize,
+    /// Warn at startup about situations sharing this much of their text (0-1);
+    /// above 1 skips the check
+    pub duplicate_situation_threshold: f32,
+    /// Highest stat a choice may require before it's flagged as unreachable
+    pub requirement_ceiling: RequirementCeiling,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            rng_seed: None,
+            finance_floor: -5000,
+            finance_cap: 1_000_000,
+            bankruptcy_scs_penalty: 150,
+            tier_d_grace: true,
+            grace_finance_cost: 500,
+            review_promotion_weight: 1,
+            review_neutral_weight: 3,
+            review_audit_weight: 1,
+            review_scs_per_weight: 50,
+            shuffle_options: false,
+            skip_option_enabled: false,
+            skip_option_scs_penalty: 5,
+            escape_acknowledges: true,
+            escape_skips: false,
+            choice_cooldown_turns: 60,
+            high_severity_spacing: 2,
+            after_hours_manipulate_risk: 5,
+            procedural_attempts: 3,
+            handcrafted_recency_window: 10,
+            min_options: 2,
+            starting_favors: 2,
+            tier_risk_curves: HashMap::from([
+                (EventDomain::Work, vec![6, 3, 0, -3, -6]),
+                (EventDomain::Family, vec![2, 1, 0, -1, -2]),
+                (EventDomain::Public, vec![6, 3, 0, -3, -6]),
+                (EventDomain::Party, vec![-4, -2, 0, 3, 6]),
+            ]),
+            risk_clamps: HashMap::from([
+                (EventDomain::Party, RiskClamp { min: 0, max: 98 }),
+                (EventDomain::Public, RiskClamp { min: 0, max: 80 }),
+            ]),
+            life_stage_ages: vec![26, 41, 56],
+            retirement_age: 65,
+            new_game_plus: CarryOver::default(),
+            guanxi_caps: GuanxiCaps::default(),
+            event_window_width_fraction: 0.4,
+            event_window_min_width: 350.0,
+            event_window_max_width: 720.0,
+            widget_style: WidgetStyle::default(),
+            log_buffer_lines: 500,
+            duplicate_situation_threshold: 0.6,
+            requirement_ceiling: RequirementCeiling::default(),
+        }
+    }
+}
+
+/// What a New Game+ keeps from the finished run
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CarryOver {
+    /// Share of the final finances (when positive) added to the new start
+    pub finance_fraction: f32,
+    /// Keep recently seen situations out of rotation in the new run
+    pub encounter_history: bool,
+    /// Keep unspent favors instead of the starting allowance
+    pub favors: bool,
+    /// Keep the legacy score, so it builds up across generations
+    pub legacy: bool,
+}
+
+impl Default for CarryOver {
+    fn default() -> Self {
+        Self {
+            finance_fraction: 0.25,
+            encounter_history: true,
+            favors: false,
+            legacy: true,
+        }
+    }
+}
+
+/// Highest each guanxi stat can be raised by outcomes (0 leaves it uncapped),
+/// and what becomes of the points gained past it. Losses are never capped.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GuanxiCaps {
+    pub family: u32,
+    pub network: u32,
+    pub party: u32,
+    pub overflow: GuanxiOverflow,
+    /// SCS per point past a cap when `overflow` is `scs`
+    pub scs_per_point: i32,
+}
+
+impl Default for GuanxiCaps {
+    fn default() -> Self {
+        Self {
+            family: 15,
+            network: 15,
+            party: 15,
+            overflow: GuanxiOverflow::Scs,
+            scs_per_point: 1,
+        }
+    }
+}
+
+/// What a guanxi gain past its cap turns into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GuanxiOverflow {
+    /// A small SCS bonus: the player is owed favors
+    Scs,
+    /// Nothing; the gain is lost
+    Clamp,
+}
+
+/// Range a choice's risk is clamped to after every adjustment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RiskClamp {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl Default for RiskClamp {
+    fn default() -> Self {
+        Self { min: 0, max: 95 }
+    }
+}
+
+/// Rough upper bound on the stats a player can have built up by a situation's
+/// last tier and life stage; requirements above it are likely dead choices
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RequirementCeiling {
+    pub base: u32,
+    pub per_tier: u32,
+    pub per_stage: u32,
+}
+
+impl Default for RequirementCeiling {
+    fn default() -> Self {
+        Self {
+            base: 3,
+            per_tier: 2,
+            per_stage: 1,
+        }
+    }
+}
+
+impl RequirementCeiling {
+    /// Ceiling for a situation whose ranges end at `tier_max` and `stage_max`
+    pub fn at(&self, tier_max: usize, stage_max: usize) -> u32 {
+        let steps = |count: usize, per: u32| (count as u32).saturating_mul(per);
+        self.base
+            .saturating_add(steps(tier_max, self.per_tier))
+            .saturating_add(steps(stage_max, self.per_stage))
+    }
+}
+
+impl GameConfig {
+    pub fn from_embedded() -> Result<Self> {
+        let config_toml = include_str!("../data/game_config.toml");
+        let config: Self =
+            toml::from_str(config_toml).context("Failed to parse game_config.toml")?;
+        ensure!(
+            config.life_stage_ages.windows(2).all(|pair| pair[0] < pair[1]),
+            "life_stage_ages must be strictly ascending: {:?}",
+            config.life_stage_ages
+        );
+        for (domain, clamp) in &config.risk_clamps {
+            ensure!(
+                clamp.min <= clamp.max && clamp.max <= MAX_RISK_CHANCE,
+                "risk_clamps.{} must satisfy min <= max <= {}: {:?}",
+                domain.as_str(),
+                MAX_RISK_CHANCE,
+                clamp
+            );
+        }
+        let finance_fraction = config.new_game_plus.finance_fraction;
+        ensure!(
+            (0.0..=1.0).contains(&finance_fraction),
+            "new_game_plus.finance_fraction must be between 0 and 1: {}",
+            finance_fraction
+        );
+        Ok(config)
+    }
+
+    /// Risk range for choices in `domain`, 0-95 unless configured
+    pub fn risk_clamp(&self, domain: EventDomain) -> RiskClamp {
+        self.risk_clamps.get(&domain).copied().unwrap_or_default()
+    }
+
+    /// Number of life stages the age thresholds define
+    pub fn life_stage_count(&self) -> usize {
+        self.life_stage_ages.len() + 1
+    }
+
+    /// Life stage (1 to `life_stage_count`) for a given age
+    pub fn life_stage_for_age(&self, age: u32) -> usize {
+        self.life_stage_ages.partition_point(|&start| start <= age) + 1
+    }
+}
@@ -0,0 +1,335 @@
+//! Developer window listing every situation in the library, with seeded
+//! sample descriptions for spot-checking content without playing to it, and
+//! the spread of events a player at a chosen position would be dealt.
+
+use super::{LotusApp, TIER_COUNT};
+use crate::game_data::preview_event_distribution;
+use crate::procedural::library::{Severity, SituationTemplate};
+use crate::procedural::{EventDomain, generate_samples};
+use eframe::egui::{self, RichText, ScrollArea, Window};
+
+/// Seed for the first batch of samples; each further click advances it by one
+pub const BROWSER_SAMPLE_SEED: u64 = 42;
+/// Events generated per click, to show the range of text a situation produces
+const BROWSER_SAMPLE_COUNT: usize = 3;
+/// Events drawn per "Simulate here" click
+const SIMULATION_SAMPLES: usize = 500;
+
+/// Criteria narrowing the browser's situation list
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserFilter {
+    pub domain: Option<EventDomain>,
+    pub severity: Option<Severity>,
+    pub tier_min: usize,
+    pub tier_max: usize,
+    pub life_stage: Option<usize>,
+    pub id_search: String,
+}
+
+impl Default for BrowserFilter {
+    fn default() -> Self {
+        Self {
+            domain: None,
+            severity: None,
+            tier_min: 0,
+            tier_max: TIER_COUNT - 1,
+            life_stage: None,
+            id_search: String::new(),
+        }
+    }
+}
+
+impl BrowserFilter {
+    /// A situation matches when its tier range overlaps the filter's and it
+    /// can occur in the chosen life stage
+    fn matches(&self, situation: &SituationTemplate) -> bool {
+        self.domain.is_none_or(|domain| situation.domain == domain)
+            && self
+                .severity
+                .is_none_or(|severity| situation.severity == severity)
+            && situation.tier_min <= self.tier_max
+            && situation.tier_max >= self.tier_min
+            && self.life_stage.is_none_or(|stage| {
+                (situation.life_stage_min..=situation.life_stage_max).contains(&stage)
+            })
+            && situation
+                .id
+                .to_lowercase()
+                .contains(&self.id_search.trim().to_lowercase())
+    }
+}
+
+/// Rendered sample events for one situation: each description followed by
+/// its choices
+pub struct BrowserSample {
+    pub situation_id: String,
+    pub seed: u64,
+    pub texts: Vec<String>,
+}
+
+/// Where the distribution preview stands its hypothetical player, and what
+/// it drew there last
+pub struct BrowserSimulation {
+    pub tier: usize,
+    pub stage: usize,
+    /// Tier and stage of the last run, if any
+    pub drawn_at: Option<(usize, usize)>,
+    /// Each event's count in the last run, most common first
+    pub counts: Vec<(String, usize)>,
+}
+
+impl Default for BrowserSimulation {
+    fn default() -> Self {
+        Self {
+            tier: 0,
+            stage: 1,
+            drawn_at: None,
+            counts: Vec::new(),
+        }
+    }
+}
+
+impl LotusApp {
+    pub(crate) fn show_content_browser(&mut self, ctx: &egui::Context) {
+        let mut open = self.content_browser_open;
+        let mut sample_request = None;
+
+        Window::new("Content Browser")
+            .open(&mut open)
+            .default_width(480.0)
+            .default_height(500.0)
+            .show(ctx, |ui| {
+                self.show_browser_filters(ui);
+                ui.collapsing("Event distribution", |ui| self.show_browser_simulation(ui));
+                let matches = self.browser_matches();
+                ui.label(format!(
+                    "{} of {} situations",
+                    matches.len(),
+                    self.situation_library.situations().len()
+                ));
+                ui.separator();
+                ScrollArea::vertical().show(ui, |ui| {
+                    for &index in &matches {
+                        let situation = &self.situation_library.situations()[index];
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&situation.id).strong());
+                            if ui.small_button("Generate samples").clicked() {
+                                sample_request = Some(situation.id.clone());
+                            }
+                        });
+                        ui.small(format!(
+                            "{} | tiers {}-{} | stages {}-{} | {:?} severity | base risk {}%",
+                            situation.domain.as_str(),
+                            situation.tier_min,
+                            situation.tier_max,
+                            situation.life_stage_min,
+                            situation.life_stage_max,
+                            situation.severity,
+                            situation.base_risk
+                        ));
+                        let archetypes: Vec<String> = situation
+                            .choices
+                            .iter()
+                            .map(|choice| format!("{:?}", choice.archetype))
+                            .collect();
+                        ui.small(format!("Choices: {}", archetypes.join(", ")));
+                        if situation.draft {
+                            ui.small(RichText::new("DRAFT").color(egui::Color32::ORANGE));
+                        }
+                        if !situation.notes.is_empty() {
+                            ui.small(RichText::new(format!("Notes: {}", situation.notes)).weak());
+                        }
+                        if let Some(sample) = &self.browser_sample
+                            && sample.situation_id == situation.id
+                        {
+                            ui.small(format!("seed {}", sample.seed));
+                            for text in &sample.texts {
+                                ui.label(RichText::new(text).italics());
+                            }
+                        }
+                        ui.separator();
+                    }
+                });
+            });
+
+        if let Some(situation_id) = sample_request {
+            self.generate_browser_sample(&situation_id);
+        }
+        self.content_browser_open = open;
+    }
+
+    fn show_browser_filters(&mut self, ui: &mut egui::Ui) {
+        let stage_count = self.config.life_stage_count();
+        let filter = &mut self.browser_filter;
+        ui.horizontal(|ui| {
+            ui.label("Search id:");
+            ui.text_edit_singleline(&mut filter.id_search);
+            if ui.button("Clear filters").clicked() {
+                *filter = BrowserFilter::default();
+            }
+        });
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Domain")
+                .selected_text(filter.domain.as_ref().map_or("Any", EventDomain::as_str))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut filter.domain, None, "Any");
+                    for domain in EventDomain::ALL {
+                        ui.selectable_value(&mut filter.domain, Some(domain), domain.as_str());
+                    }
+                });
+            egui::ComboBox::from_label("Severity")
+                .selected_text(
+                    filter
+                        .severity
+                        .map_or("Any".to_string(), |severity| format!("{:?}", severity)),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut filter.severity, None, "Any");
+                    for severity in Severity::ALL {
+                        ui.selectable_value(
+                            &mut filter.severity,
+                            Some(severity),
+                            format!("{:?}", severity),
+                        );
+                    }
+                });
+            egui::ComboBox::from_label("Stage")
+                .selected_text(
+                    filter
+                        .life_stage
+                        .map_or("Any".to_string(), |stage| stage.to_string()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut filter.life_stage, None, "Any");
+                    for stage in 1..=stage_count {
+                        ui.selectable_value(&mut filter.life_stage, Some(stage), stage.to_string());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut filter.tier_min, 0..=TIER_COUNT - 1).text("Tier min"));
+            ui.add(egui::Slider::new(&mut filter.tier_max, 0..=TIER_COUNT - 1).text("Tier max"));
+        });
+        if filter.tier_min > filter.tier_max {
+            filter.tier_max = filter.tier_min;
+        }
+    }
+
+    /// Position controls, the "Simulate here" button and the last tally
+    fn show_browser_simulation(&mut self, ui: &mut egui::Ui) {
+        let stage_count = self.config.life_stage_count();
+        let simulation = &mut self.browser_simulation;
+        let mut simulate = false;
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut simulation.tier, 0..=TIER_COUNT - 1).text("Tier"));
+            ui.add(egui::Slider::new(&mut simulation.stage, 1..=stage_count).text("Stage"));
+            simulate = ui
+                .button("Simulate here")
+                .on_hover_text(format!(
+                    "Draw {} events as a player at this position",
+                    SIMULATION_SAMPLES
+                ))
+                .clicked();
+        });
+        if simulate {
+            let (tier, stage) = (simulation.tier, simulation.stage);
+            let mut counts: Vec<(String, usize)> = preview_event_distribution(
+                self,
+                tier,
+                stage,
+                SIMULATION_SAMPLES,
+                BROWSER_SAMPLE_SEED,
+            )
+            .into_iter()
+            .collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            self.browser_simulation.drawn_at = Some((tier, stage));
+            self.browser_simulation.counts = counts;
+        }
+
+        let Some((tier, stage)) = self.browser_simulation.drawn_at else {
+            return;
+        };
+        let counts = &self.browser_simulation.counts;
+        ui.small(format!(
+            "{} draws at tier {}, stage {}: {} different events",
+            SIMULATION_SAMPLES,
+            tier,
+            stage,
+            counts.len()
+        ));
+        ScrollArea::vertical()
+            .id_salt("browser_distribution")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                egui::Grid::new("browser_distribution_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (id, count) in counts {
+                            ui.label(id);
+                            ui.label(count.to_string());
+                            ui.label(format!(
+                                "{:.1}%",
+                                *count as f32 * 100.0 / SIMULATION_SAMPLES as f32
+                            ));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Indices of the situations matching the current filter. The list is
+    /// cached and only recomputed when the filter changes.
+    fn browser_matches(&mut self) -> Vec<usize> {
+        if let Some((filter, matches)) = &self.browser_matches
+            && *filter == self.browser_filter
+        {
+            return matches.clone();
+        }
+        let matches: Vec<usize> = self
+            .situation_library
+            .situations()
+            .iter()
+            .enumerate()
+            .filter(|(_, situation)| self.browser_filter.matches(situation))
+            .map(|(index, _)| index)
+            .collect();
+        self.browser_matches = Some((self.browser_filter.clone(), matches.clone()));
+        matches
+    }
+
+    /// Generates a batch of events from the situation at a tier it can appear
+    /// in, closest to the player's own
+    fn generate_browser_sample(&mut self, situation_id: &str) {
+        let Some(situation) = self.situation_library.situation_by_id(situation_id) else {
+            return;
+        };
+        let seed = match &self.browser_sample {
+            Some(sample) if sample.situation_id == situation_id => sample.seed + 1,
+            _ => BROWSER_SAMPLE_SEED,
+        };
+        let tier = self
+            .player_tier
+            .clamp(situation.tier_min, situation.tier_max);
+        let texts = match generate_samples(self, situation_id, tier, BROWSER_SAMPLE_COUNT, seed) {
+            Ok(events) => events
+                .iter()
+                .map(|event| {
+                    let choices: Vec<String> = event
+                        .options
+                        .iter()
+                        .map(|option| format!("\n  - {}", option.text))
+                        .collect();
+                    format!("{}{}", event.description, choices.concat())
+                })
+                .collect(),
+            Err(e) => vec![format!("Error: {:#}", e)],
+        };
+
+        self.browser_sample = Some(BrowserSample {
+            situation_id: situation_id.to_string(),
+            seed,
+            texts,
+        });
+    }
+}
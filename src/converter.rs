@@ -1,17 +1,22 @@
-use crate::game_data::{EventData, EventOption, EventOutcome};
-use anyhow::Result;
-use serde::Deserialize;
+use crate::game_data::{
+    EVENTS_VERSION, EventData, EventFile, EventOption, EventOutcome, parse_event_database,
+};
+use crate::paths::Paths;
+use crate::requirements::{RequirementKey, Requirements};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
 // --- Configuration ---
-const EVENTS_CSV_PATH: &str = "data/events.csv";
-const OPTIONS_CSV_PATH: &str = "data/event_options.csv";
+/// Where `--convert` writes and `--export` reads unless `Paths` overrides it
 const JSON_OUTPUT_PATH: &str = "src/events.json";
 // ---------------------
 
-/// Represents a row in the `events.csv` file.
-#[derive(Debug, Deserialize)]
+/// Represents a row in the `events.csv` file. Field order is the column
+/// order `--export` writes.
+#[derive(Debug, Deserialize, Serialize)]
 struct EventCsvRow {
     event_id: String,
     title: String,
@@ -20,10 +25,14 @@ struct EventCsvRow {
     max_tier: usize,
     is_generic: bool,
     life_stage: usize, // NEW: Added life_stage
+    #[serde(default)]
+    icon: Option<String>, // Optional header image, see `icons`
+    #[serde(default)]
+    notes: String, // Optional author notes, kept through the round trip
 }
 
 /// Represents a row in the `event_options.csv` file.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct OptionCsvRow {
     event_id: String,
     text: String,
@@ -41,6 +50,8 @@ struct OptionCsvRow {
     guanxi_network_change: i32,
     #[serde(default)]
     guanxi_party_change: i32,
+    #[serde(default)]
+    favor_change: i32,
 
     // Requirements
     #[serde(default)]
@@ -49,6 +60,9 @@ struct OptionCsvRow {
     req_guanxi_network: u32,
     #[serde(default)]
     req_guanxi_party: u32,
+    /// Any-of groups, e.g. `connected(guanxi_party=3|guanxi_network=3)`
+    #[serde(default)]
+    req_any_of: String,
 
     // Risk & Failure Outcome
     #[serde(default)]
@@ -69,6 +83,11 @@ struct OptionCsvRow {
     fail_guanxi_network_change: i32,
     #[serde(default)]
     fail_guanxi_party_change: i32,
+    #[serde(default)]
+    fail_favor_change: i32,
+
+    #[serde(default)]
+    notes: String,
 }
 
 /// Helper to build an EventData from a CSV row.
@@ -80,14 +99,17 @@ fn create_event_from_row(row: EventCsvRow) -> EventData {
         max_tier: row.max_tier,
         is_generic: row.is_generic,
         life_stage: row.life_stage, // NEW: Pass life_stage
-        options: Vec::new(),        // Will be populated from the other file
+        icon: row.icon.filter(|icon| !icon.is_empty()),
+        options: Vec::new(), // Will be populated from the other file
+        description_parts: Vec::new(),
+        notes: row.notes,
         procedural_id: None,
         procedural_domain: None,
     }
 }
 
 /// Helper to build an EventOption from a CSV row.
-fn create_option_from_row(row: OptionCsvRow) -> EventOption {
+fn create_option_from_row(row: OptionCsvRow) -> Result<EventOption> {
     let success_outcome = EventOutcome {
         scs_change: row.scs_change,
         finance_change: row.finance_change,
@@ -95,36 +117,36 @@ fn create_option_from_row(row: OptionCsvRow) -> EventOption {
         guanxi_family_change: row.guanxi_family_change,
         guanxi_network_change: row.guanxi_network_change,
         guanxi_party_change: row.guanxi_party_change,
+        favor_change: row.favor_change,
     };
 
-    let mut requirements = HashMap::new();
+    let mut requirements = Requirements::new();
     if row.req_guanxi_family > 0 {
-        requirements.insert("guanxi_family".to_string(), row.req_guanxi_family);
+        requirements.insert(RequirementKey::GuanxiFamily, row.req_guanxi_family);
     }
     if row.req_guanxi_network > 0 {
-        requirements.insert("guanxi_network".to_string(), row.req_guanxi_network);
+        requirements.insert(RequirementKey::GuanxiNetwork, row.req_guanxi_network);
     }
     if row.req_guanxi_party > 0 {
-        requirements.insert("guanxi_party".to_string(), row.req_guanxi_party);
+        requirements.insert(RequirementKey::GuanxiParty, row.req_guanxi_party);
     }
+    requirements
+        .parse_groups(&row.req_any_of)
+        .with_context(|| format!("option '{}'", row.text))?;
 
-    let mut failure_outcome = None;
-    if row.risk_chance > 0 {
-        let outcome = EventOutcome {
-            scs_change: row.fail_scs_change,
-            finance_change: row.fail_finance_change,
-            career_level_change: row.fail_career_level_change,
-            guanxi_family_change: row.fail_guanxi_family_change,
-            guanxi_network_change: row.fail_guanxi_network_change,
-            guanxi_party_change: row.fail_guanxi_party_change,
-        };
-        // Only set the failure outcome if it's actually different from success
-        if outcome != success_outcome || !row.failure_result_text.is_empty() {
-            failure_outcome = Some(outcome);
-        }
-    }
+    // Every risky option gets a failure outcome, even one matching success,
+    // so a failed roll always applies something
+    let failure_outcome = (row.risk_chance > 0).then_some(EventOutcome {
+        scs_change: row.fail_scs_change,
+        finance_change: row.fail_finance_change,
+        career_level_change: row.fail_career_level_change,
+        guanxi_family_change: row.fail_guanxi_family_change,
+        guanxi_network_change: row.fail_guanxi_network_change,
+        guanxi_party_change: row.fail_guanxi_party_change,
+        favor_change: row.fail_favor_change,
+    });
 
-    EventOption {
+    Ok(EventOption {
         text: row.text,
         requirements,
         risk_chance: row.risk_chance,
@@ -132,26 +154,100 @@ fn create_option_from_row(row: OptionCsvRow) -> EventOption {
         success_result: row.success_result_text,
         failure_outcome,
         failure_result: row.failure_result_text,
+        archetype: None,
+        risk_breakdown: None,
+        notes: row.notes,
+        is_skip: false,
+    })
+}
+
+/// Inverse of `create_event_from_row`
+fn row_from_event(event_id: &str, event: &EventData) -> EventCsvRow {
+    EventCsvRow {
+        event_id: event_id.to_string(),
+        title: event.title.clone(),
+        description: event.description.clone(),
+        min_tier: event.min_tier,
+        max_tier: event.max_tier,
+        is_generic: event.is_generic,
+        life_stage: event.life_stage,
+        icon: event.icon.clone(),
+        notes: event.notes.clone(),
     }
 }
 
+/// Inverse of `create_option_from_row`
+fn row_from_option(event_id: &str, option: &EventOption) -> OptionCsvRow {
+    let success = &option.success_outcome;
+    let failure = option.failure_outcome.clone().unwrap_or_default();
+    OptionCsvRow {
+        event_id: event_id.to_string(),
+        text: option.text.clone(),
+        scs_change: success.scs_change,
+        finance_change: success.finance_change,
+        career_level_change: success.career_level_change,
+        guanxi_family_change: success.guanxi_family_change,
+        guanxi_network_change: success.guanxi_network_change,
+        guanxi_party_change: success.guanxi_party_change,
+        favor_change: success.favor_change,
+        req_guanxi_family: option.requirements.get(RequirementKey::GuanxiFamily),
+        req_guanxi_network: option.requirements.get(RequirementKey::GuanxiNetwork),
+        req_guanxi_party: option.requirements.get(RequirementKey::GuanxiParty),
+        req_any_of: option.requirements.format_groups(),
+        risk_chance: option.risk_chance,
+        success_result_text: option.success_result.clone(),
+        failure_result_text: option.failure_result.clone(),
+        fail_scs_change: failure.scs_change,
+        fail_finance_change: failure.finance_change,
+        fail_career_level_change: failure.career_level_change,
+        fail_guanxi_family_change: failure.guanxi_family_change,
+        fail_guanxi_network_change: failure.guanxi_network_change,
+        fail_guanxi_party_change: failure.guanxi_party_change,
+        fail_favor_change: failure.favor_change,
+        notes: option.notes.clone(),
+    }
+}
+
+/// The events JSON `--convert` writes and `--export` reads
+fn json_path(paths: &Paths) -> &Path {
+    paths
+        .events_json
+        .as_deref()
+        .unwrap_or(Path::new(JSON_OUTPUT_PATH))
+}
+
 /// Main converter function, called from `main.rs`.
-pub fn run_converter() -> Result<()> {
+pub fn run_converter(paths: &Paths) -> Result<()> {
+    let final_event_list = events_from_csv(paths)?;
+
+    // 4. Write the final JSON file
+    let event_file = EventFile {
+        version: EVENTS_VERSION,
+        events: final_event_list,
+    };
+    let json_string = serde_json::to_string_pretty(&event_file)?;
+    fs::write(json_path(paths), json_string)?;
+
+    Ok(())
+}
+
+/// Reads and validates the events in both CSVs without writing anything
+pub fn events_from_csv(paths: &Paths) -> Result<Vec<EventData>> {
     let mut events = HashMap::<String, EventData>::new();
 
     // 1. Read all events
-    let mut event_reader = csv::Reader::from_path(EVENTS_CSV_PATH)?;
+    let mut event_reader = csv::Reader::from_path(&paths.events_csv)?;
     for result in event_reader.deserialize() {
         let row: EventCsvRow = result?;
         events.insert(row.event_id.clone(), create_event_from_row(row));
     }
 
     // 2. Read all options and attach them to their events
-    let mut option_reader = csv::Reader::from_path(OPTIONS_CSV_PATH)?;
+    let mut option_reader = csv::Reader::from_path(&paths.options_csv)?;
     for result in option_reader.deserialize() {
         let row: OptionCsvRow = result?;
         if let Some(event) = events.get_mut(&row.event_id) {
-            event.options.push(create_option_from_row(row));
+            event.options.push(create_option_from_row(row)?);
         } else {
             eprintln!(
                 "Warning: Option found for non-existent event_id {}",
@@ -162,10 +258,29 @@ pub fn run_converter() -> Result<()> {
 
     // 3. Convert the events HashMap to a Vec for the final JSON
     let final_event_list: Vec<EventData> = events.into_values().collect();
+    for event in &final_event_list {
+        event.validate()?;
+    }
+    Ok(final_event_list)
+}
 
-    // 4. Write the final JSON file
-    let json_string = serde_json::to_string_pretty(&final_event_list)?;
-    fs::write(JSON_OUTPUT_PATH, json_string)?;
+/// Export function, called from `main.rs`: the reverse of `run_converter`,
+/// rewriting both CSVs from `events.json` with their notes. Events are
+/// numbered from 1 in file order, since the JSON keeps no event ids.
+pub fn run_exporter(paths: &Paths) -> Result<()> {
+    let events = parse_event_database(&fs::read_to_string(json_path(paths))?)?;
+
+    let mut event_writer = csv::Writer::from_path(&paths.events_csv)?;
+    let mut option_writer = csv::Writer::from_path(&paths.options_csv)?;
+    for (i, event) in events.iter().enumerate() {
+        let event_id = (i + 1).to_string();
+        event_writer.serialize(row_from_event(&event_id, event))?;
+        for option in &event.options {
+            option_writer.serialize(row_from_option(&event_id, option))?;
+        }
+    }
+    event_writer.flush()?;
+    option_writer.flush()?;
 
     Ok(())
 }
@@ -0,0 +1,240 @@
+//! Line-based debug commands that drive an `Engine` and answer in JSON, for
+//! scripts exercising the full game loop without egui. With the
+//! `debug-socket` feature, `serve` accepts them over a local TCP socket:
+//!
+//! ```text
+//! state | event | history | move cw | move ccw | choose 1 [favor] | set scs 600 | quit
+//! ```
+//!
+//! Every reply is one JSON line: `{"ok":true,"result":...}` or
+//! `{"ok":false,"error":"..."}`.
+
+use crate::app::tier_for_scs;
+use crate::engine::{Action, Engine, StepResult};
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Value, json};
+
+/// Stats `set` can change, by the name the command uses
+const SETTABLE: [&str; 9] = [
+    "scs", "finances", "career", "family", "network", "party", "favors", "age", "tier",
+];
+
+/// Runs one command line against `engine` and returns its JSON reply
+pub fn run_command(engine: &mut Engine, line: &str) -> Value {
+    match execute(engine, line) {
+        Ok(result) => json!({ "ok": true, "result": result }),
+        Err(e) => json!({ "ok": false, "error": format!("{:#}", e) }),
+    }
+}
+
+fn execute(engine: &mut Engine, line: &str) -> Result<Value> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["state"] => Ok(serde_json::to_value(engine.state())?),
+        ["event"] => Ok(serde_json::to_value(engine.current_event())?),
+        ["history"] => Ok(json!(engine.history())),
+        ["move", direction] => {
+            let clockwise = match *direction {
+                "cw" => true,
+                "ccw" => false,
+                other => bail!("move takes cw or ccw, not '{}'", other),
+            };
+            step_json(engine.step(Action::Move { clockwise })?)
+        }
+        ["choose", number, rest @ ..] => {
+            // Numbered from 1, like the number keys in the event window
+            let number: usize = number.parse().context("choose takes an option number")?;
+            let index = number.checked_sub(1).ok_or_else(|| anyhow!("options start at 1"))?;
+            let spend_favor = match rest {
+                [] => false,
+                ["favor"] => true,
+                _ => bail!("choose takes an option number and optionally 'favor'"),
+            };
+            step_json(engine.step(Action::Choose { index, spend_favor })?)
+        }
+        ["set", stat, value] => {
+            let value: i32 = value.parse().context("set takes a whole number")?;
+            set_stat(engine, stat, value)?;
+            Ok(serde_json::to_value(engine.state())?)
+        }
+        [] => bail!("empty command"),
+        _ => bail!("unknown command '{}'", line.trim()),
+    }
+}
+
+fn step_json(result: StepResult) -> Result<Value> {
+    Ok(match result {
+        StepResult::Event(event) => json!({ "event": event }),
+        StepResult::NoEvent(error) => json!({ "no_event": error.to_string() }),
+        StepResult::Resolved {
+            resolution,
+            next_event,
+        } => {
+            let (result, outcome) = resolution.applied.unzip();
+            json!({
+                "resolved": {
+                    "succeeded": resolution.succeeded,
+                    "result": result,
+                    "outcome": outcome,
+                },
+                "next_event": next_event,
+            })
+        }
+        StepResult::Retired => json!({ "retired": true }),
+    })
+}
+
+/// Overwrites one player stat. SCS also moves the player to its tier, as a
+/// review would, and age to its life stage.
+fn set_stat(engine: &mut Engine, stat: &str, value: i32) -> Result<()> {
+    let app = engine.app_mut();
+    let unsigned = || u32::try_from(value).with_context(|| format!("{} can't be negative", stat));
+    match stat {
+        "scs" => {
+            app.social_credit_score = value;
+            app.player_tier = tier_for_scs(value);
+        }
+        "finances" => app.finances = value,
+        "career" => app.career_level = unsigned()?,
+        "family" => app.guanxi_family = unsigned()?,
+        "network" => app.guanxi_network = unsigned()?,
+        "party" => app.guanxi_party = unsigned()?,
+        "favors" => app.favors = unsigned()?,
+        "age" => {
+            app.player_age = unsigned()?;
+            app.life_stage = app.config.life_stage_for_age(app.player_age);
+        }
+        "tier" => {
+            let tier = unsigned()? as usize;
+            if tier >= crate::TIER_COUNT {
+                bail!("tier must be below {}", crate::TIER_COUNT);
+            }
+            app.player_tier = tier;
+        }
+        other => bail!("can't set '{}'; try one of {}", other, SETTABLE.join(", ")),
+    }
+    Ok(())
+}
+
+/// Answers debug commands from local TCP clients, one at a time, against a
+/// single engine until a client sends `quit`. Only loopback addresses are
+/// accepted, and a client's connection failing only drops that client.
+#[cfg(feature = "debug-socket")]
+pub fn serve(address: &str, mut engine: Engine) -> Result<()> {
+    use anyhow::ensure;
+    use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+
+    let addresses: Vec<SocketAddr> = address
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid debug socket address {}", address))?
+        .collect();
+    ensure!(
+        !addresses.is_empty() && addresses.iter().all(|a| a.ip().is_loopback()),
+        "The debug socket only listens on loopback addresses, not {}",
+        address
+    );
+    let listener = TcpListener::bind(&addresses[..])
+        .with_context(|| format!("Failed to listen on {}", address))?;
+    log::info!("Debug socket listening on {}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("Debug client failed to connect: {}", e);
+                continue;
+            }
+        };
+        match serve_client(stream, &mut engine) {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => log::warn!("Dropped debug client: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Answers one client's lines until it disconnects; true if it sent `quit`
+#[cfg(feature = "debug-socket")]
+fn serve_client(stream: std::net::TcpStream, engine: &mut Engine) -> std::io::Result<bool> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let peer = stream.peer_addr()?;
+    log::info!("Debug client connected from {}", peer);
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim() == "quit" {
+            log::info!("Debug client {} ended the session", peer);
+            return Ok(true);
+        }
+        writeln!(writer, "{}", run_command(engine, &line))?;
+    }
+    log::info!("Debug client {} disconnected", peer);
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::fixture::TEST_SEED;
+    use crate::paths::Paths;
+
+    fn engine() -> Result<Engine> {
+        let mut config = GameConfig::from_embedded()?;
+        config.rng_seed = Some(TEST_SEED);
+        Engine::new(config, &Paths::default())
+    }
+
+    #[test]
+    fn setting_scs_moves_the_tier() -> Result<()> {
+        let state = run_command(&mut engine()?, "set scs 600");
+        assert_eq!(state["result"]["social_credit_score"], 600);
+        assert_eq!(state["result"]["player_tier"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn setting_age_moves_the_life_stage() -> Result<()> {
+        let mut engine = engine()?;
+        let config = GameConfig::from_embedded()?;
+        for age in [0, config.life_stage_ages[0], config.retirement_age - 1] {
+            let state = run_command(&mut engine, &format!("set age {}", age));
+            assert_eq!(state["result"]["player_age"], age);
+            assert_eq!(state["result"]["life_stage"], config.life_stage_for_age(age));
+        }
+        assert_eq!(run_command(&mut engine, "set age 30")["result"]["life_stage"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn commands_drive_a_turn() -> Result<()> {
+        let mut engine = engine()?;
+        assert!(!run_command(&mut engine, "move cw")["result"]["event"].is_null());
+        let chosen = run_command(&mut engine, "choose 1");
+        assert_eq!(chosen["ok"], true, "{}", chosen);
+        assert!(!chosen["result"]["resolved"].is_null(), "{}", chosen);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input_gets_an_error_reply() -> Result<()> {
+        let mut engine = engine()?;
+        for bad in ["", "fly", "move up", "choose 0", "choose 99", "set scs lots", "set luck 3"] {
+            let reply = run_command(&mut engine, bad);
+            assert_eq!(reply["ok"], false, "'{}' gave {}", bad, reply);
+            assert!(reply["error"].is_string(), "'{}' gave {}", bad, reply);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "debug-socket")]
+    #[test]
+    fn serve_refuses_other_hosts() -> Result<()> {
+        for address in ["0.0.0.0:0", "192.0.2.1:7878"] {
+            let error = serve(address, engine()?).unwrap_err();
+            assert!(error.to_string().contains("loopback"), "{}: {}", address, error);
+        }
+        Ok(())
+    }
+}
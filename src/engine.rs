@@ -0,0 +1,245 @@
+//! Headless driver for the game rules, for embedding Lotus without the egui
+//! front end. The engine owns the same `LotusApp` state the UI plays on and
+//! advances it through the same turn actions, one `Action` at a time.
+
+use crate::config::GameConfig;
+use crate::game_data::{
+    EventData, EventOutcome, GenerationError, load_event_database, load_event_database_from,
+};
+use crate::lotus_widget::BoardTheme;
+use crate::paths::Paths;
+use crate::procedural::SituationLibrary;
+use crate::procedural::risk_calculator::PlayerStats;
+use crate::save::SaveState;
+use crate::scenario::load_scenarios;
+use crate::{GameState, LotusApp};
+use anyhow::{Result, bail};
+
+/// One thing the player does on their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Step one petal around the current tier
+    Move { clockwise: bool },
+    /// Resolve the open event with the option at `index`, optionally calling
+    /// in a favor to skip its risk roll
+    Choose { index: usize, spend_favor: bool },
+}
+
+/// How a chosen option played out
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    /// None for options without risk
+    pub succeeded: Option<bool>,
+    /// Result text and stat changes, unless a failure had no effect
+    pub applied: Option<(String, EventOutcome)>,
+}
+
+/// What an action led to
+#[derive(Debug, Clone)]
+pub enum StepResult {
+    /// A move opened this event; answer it with `Action::Choose`
+    Event(EventData),
+    /// A move found no event to open
+    NoEvent(GenerationError),
+    /// The open event resolved. A follow-up such as bankruptcy opens at once.
+    Resolved {
+        resolution: Resolution,
+        next_event: Option<EventData>,
+    },
+    /// The player reached retirement age and the run is over
+    Retired,
+}
+
+pub struct Engine {
+    app: LotusApp,
+}
+
+impl Engine {
+    /// Starts a run from the first scenario preset, reading events and
+    /// situations from `paths` (the embedded content unless overridden).
+    /// Set `rng_seed` in the config for a reproducible run.
+    pub fn new(config: GameConfig, paths: &Paths) -> Result<Self> {
+        let events = match &paths.events_json {
+            Some(path) => load_event_database_from(path)?,
+            None => load_event_database()?,
+        };
+        let library = match &paths.procedural_dir {
+            Some(dir) => SituationLibrary::from_dir(dir)?,
+            None => SituationLibrary::from_embedded_configs()?,
+        };
+        library.check_life_stages(config.life_stage_count())?;
+        library.check_tiers()?;
+        let mut app = LotusApp::new(
+            config,
+            events,
+            library,
+            load_scenarios()?,
+            BoardTheme::from_embedded()?,
+        );
+        app.apply_scenario();
+        app.game_state = GameState::Playing;
+        Ok(Self { app })
+    }
+
+    /// Applies one action. Actions that don't fit the current state (moving
+    /// with an event open, an option the player doesn't qualify for) are
+    /// rejected without changing anything.
+    pub fn step(&mut self, action: Action) -> Result<StepResult> {
+        if self.app.game_state == GameState::Summary {
+            bail!("The run is over");
+        }
+        match action {
+            Action::Move { clockwise } => {
+                if self.app.current_event.is_some() {
+                    bail!("An event is open; choose one of its options first");
+                }
+                self.app.move_player(clockwise);
+                if self.app.game_state == GameState::Summary {
+                    return Ok(StepResult::Retired);
+                }
+                if let Some(error) = self.app.generation_error.take() {
+                    return Ok(StepResult::NoEvent(error));
+                }
+                match &self.app.current_event {
+                    Some(event) => Ok(StepResult::Event(event.clone())),
+                    None => bail!("Moving opened no event"),
+                }
+            }
+            Action::Choose { index, spend_favor } => {
+                let Some(event) = &self.app.current_event else {
+                    bail!("No event is open");
+                };
+                let Some(option) = event.options.get(index) else {
+                    bail!(
+                        "Option {} does not exist; the event has {}",
+                        index,
+                        event.options.len()
+                    );
+                };
+                if !PlayerStats::from(&self.app).meets(&option.requirements) {
+                    bail!("The requirements for option {} are not met", index);
+                }
+                if spend_favor && (option.risk_chance == 0 || self.app.favors == 0) {
+                    bail!("A favor can only be spent on a risky option, and none are left");
+                }
+
+                let Some(resolution) = self.app.resolve_option(index, spend_favor) else {
+                    bail!("No event is open");
+                };
+                if self.app.is_review_petal(self.app.player_petal) {
+                    self.app.apply_tier_review();
+                }
+                self.app.queue_bankruptcy_event();
+                Ok(StepResult::Resolved {
+                    resolution,
+                    next_event: self.app.current_event.clone(),
+                })
+            }
+        }
+    }
+
+    /// The event waiting for a `Choose`, if any
+    pub fn current_event(&self) -> Option<&EventData> {
+        self.app.current_event.as_ref()
+    }
+
+    /// Every player and run stat, in the form a save game stores them
+    pub fn state(&self) -> SaveState {
+        SaveState::capture(&self.app)
+    }
+
+    /// History log, oldest entry first
+    pub fn history(&self) -> &[String] {
+        &self.app.history
+    }
+
+    /// Direct access to the game state, for debug commands that set stats
+    pub(crate) fn app_mut(&mut self) -> &mut LotusApp {
+        &mut self.app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TEST_SEED;
+    use crate::game_data::EVENTS_VERSION;
+    use crate::save::parse_save;
+    use crate::stats::STAT_HISTORY_SIZE;
+
+    /// Moves clockwise and takes the first option the player qualifies for
+    fn play(actions: usize) -> Result<Engine> {
+        let mut config = GameConfig::from_embedded()?;
+        config.rng_seed = Some(TEST_SEED);
+        let mut engine = Engine::new(config, &Paths::default())?;
+        for _ in 0..actions {
+            let result = match engine.current_event() {
+                None => engine.step(Action::Move { clockwise: true })?,
+                Some(event) => (0..event.options.len())
+                    .find_map(|index| {
+                        let spend_favor = false;
+                        engine.step(Action::Choose { index, spend_favor }).ok()
+                    })
+                    .expect("no option could be taken"),
+            };
+            if let StepResult::Retired = result {
+                break;
+            }
+        }
+        Ok(engine)
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_run() -> Result<()> {
+        assert_eq!(play(300)?.history(), play(300)?.history());
+        Ok(())
+    }
+
+    /// One snapshot per turn plus the start, up to the cap, ending on the
+    /// current stats and surviving a save
+    #[test]
+    fn stat_history_tracks_each_turn() -> Result<()> {
+        let state = play(300)?.state();
+        let history = &state.stat_history;
+        assert_eq!(history.len(), (state.turn as usize + 1).min(STAT_HISTORY_SIZE));
+        let last = history.back().expect("no snapshots");
+        assert_eq!(
+            (last.turn, last.scs, last.finances),
+            (state.turn, state.social_credit_score, state.finances)
+        );
+        let reloaded = parse_save(&serde_json::to_string(&state)?)?;
+        assert_eq!(reloaded.stat_history, *history);
+        Ok(())
+    }
+
+    /// Content overrides reach the engine instead of the embedded content
+    #[test]
+    fn content_paths_are_used() -> Result<()> {
+        let events = load_event_database()?;
+        let file = std::env::temp_dir().join(format!("lotus_engine_{}.json", std::process::id()));
+        let document = serde_json::json!({ "version": EVENTS_VERSION, "events": &events[..1] });
+        std::fs::write(&file, document.to_string())?;
+        let paths = Paths {
+            events_json: Some(file.clone()),
+            procedural_dir: Some("data/procedural".into()),
+            ..Paths::default()
+        };
+        let engine = Engine::new(GameConfig::from_embedded()?, &paths);
+        std::fs::remove_file(&file)?;
+        assert_eq!(engine?.app.event_database.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_actions_that_do_not_fit() -> Result<()> {
+        let mut engine = Engine::new(GameConfig::from_embedded()?, &Paths::default())?;
+        let spend_favor = false;
+        assert!(engine.step(Action::Choose { index: 0, spend_favor }).is_err());
+        engine.step(Action::Move { clockwise: true })?;
+        if engine.current_event().is_some() {
+            assert!(engine.step(Action::Move { clockwise: false }).is_err());
+            assert!(engine.step(Action::Choose { index: 99, spend_favor }).is_err());
+        }
+        Ok(())
+    }
+}
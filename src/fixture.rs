@@ -0,0 +1,71 @@
+//! The game audits and tests start from: the embedded rules and content,
+//! with whichever part a check exercises swapped out.
+
+use crate::LotusApp;
+use crate::config::GameConfig;
+use crate::game_data::{EventData, load_event_database};
+use crate::lotus_widget::BoardTheme;
+use crate::procedural::SituationLibrary;
+#[cfg(test)]
+use crate::procedural::library::VariableLibraries;
+use crate::scenario::load_scenarios;
+use anyhow::Result;
+
+/// Seed for tests that need a reproducible RNG
+#[cfg(test)]
+pub const TEST_SEED: u64 = 42;
+
+/// A few known entries per variable, so a test can tell what resolved
+#[cfg(test)]
+const TEST_VARIABLES: &str = r#"
+excuse_library = ["my bus broke down"]
+relationship_types = ["cousin"]
+bribe_amount = ["50 yuan", "200 yuan", "1,000 yuan"]
+public_place = ["the market", "the station"]
+
+[colleague_descriptors]
+"0" = ["the new apprentice"]
+"2" = ["the section chief"]
+"4" = ["the provincial director"]
+"#;
+
+/// A small variable library with the entries in `TEST_VARIABLES`
+#[cfg(test)]
+pub fn test_variables() -> VariableLibraries {
+    toml::from_str(TEST_VARIABLES).expect("test variables should parse")
+}
+
+/// The parts of a game a check may replace; scenarios and the board theme
+/// are always the embedded ones
+pub(crate) struct Fixture {
+    pub config: GameConfig,
+    pub events: Vec<EventData>,
+    pub library: SituationLibrary,
+}
+
+impl Fixture {
+    /// Everything embedded, for tests that don't swap out the library
+    #[cfg(test)]
+    pub fn new() -> Result<Self> {
+        Self::embedded(SituationLibrary::from_embedded_configs()?)
+    }
+
+    /// The embedded config and handcrafted events around `library`
+    pub fn embedded(library: SituationLibrary) -> Result<Self> {
+        Ok(Self {
+            config: GameConfig::from_embedded()?,
+            events: load_event_database()?,
+            library,
+        })
+    }
+
+    pub fn build(self) -> Result<LotusApp> {
+        Ok(LotusApp::new(
+            self.config,
+            self.events,
+            self.library,
+            load_scenarios()?,
+            BoardTheme::from_embedded()?,
+        ))
+    }
+}
@@ -1,7 +1,17 @@
-use super::LotusApp;
+use super::{LotusApp, TIER_COUNT};
+use crate::config::GameConfig;
 use crate::procedural;
+use crate::procedural::{AttemptTrace, EventDomain};
+use crate::procedural::library::ChoiceType;
+use crate::procedural::generator::placement_range;
+use crate::procedural::risk_calculator::{PlayerStats, RiskBreakdown};
+use crate::procedural::text_assembly::DescriptionPart;
+use crate::requirements::{RequirementKey, Requirements};
+use crate::schema::{self, Migration};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 // --- Core Data Structures ---
 
@@ -15,18 +25,60 @@ pub struct EventOutcome {
     pub guanxi_family_change: i32,
     pub guanxi_network_change: i32,
     pub guanxi_party_change: i32,
+    pub favor_change: i32,
 }
 
+impl EventOption {
+    /// A risky option must say what happens when it fails, so a failed roll
+    /// is never silently a no-op, and its risk must be a percentage
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.risk_chance <= MAX_RISK_CHANCE,
+            "option '{}' has a {}% risk; risk_chance is a percentage (0-{})",
+            self.text,
+            self.risk_chance,
+            MAX_RISK_CHANCE
+        );
+        anyhow::ensure!(
+            self.risk_chance == 0 || self.failure_outcome.is_some(),
+            "option '{}' has a {}% risk but no failure outcome",
+            self.text,
+            self.risk_chance
+        );
+        Ok(())
+    }
+}
+
+impl EventOutcome {
+    /// Net change of several effects applied together by one choice
+    pub fn net(effects: &[EventOutcome]) -> Self {
+        let mut net = Self::default();
+        for effect in effects {
+            net.scs_change += effect.scs_change;
+            net.finance_change += effect.finance_change;
+            net.career_level_change += effect.career_level_change;
+            net.guanxi_family_change += effect.guanxi_family_change;
+            net.guanxi_network_change += effect.guanxi_network_change;
+            net.guanxi_party_change += effect.guanxi_party_change;
+            net.favor_change += effect.favor_change;
+        }
+        net
+    }
+}
+
+/// A `risk_chance` that always fails
+pub const MAX_RISK_CHANCE: u8 = 100;
+
 /// A single choice in an event, pairing text with its outcome.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EventOption {
     pub text: String,
     #[serde(default)]
-    pub requirements: HashMap<String, u32>,
+    pub requirements: Requirements,
 
     // Risk and multiple outcomes
     #[serde(default)]
-    pub risk_chance: u8, // Chance of failure (0-100)
+    pub risk_chance: u8, // Percent chance of failure: 0 never fails, 100 always does
 
     pub success_outcome: EventOutcome,
     pub success_result: String, // Text to show on success
@@ -37,6 +89,25 @@ pub struct EventOption {
     #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub failure_result: String, // Text to show on failure
+
+    // Archetype of a procedural choice, remembered for narrative callbacks
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archetype: Option<ChoiceType>,
+    /// How a procedural choice's risk was calculated, for the tooltip
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_breakdown: Option<RiskBreakdown>,
+
+    /// Author notes carried through the CSV round trip; never used in play
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+
+    /// Marks the synthetic "Do nothing" option, the one Escape may pick
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_skip: bool,
 }
 
 /// The main event struct, holding all data for a modal window.
@@ -49,6 +120,10 @@ pub struct EventData {
     pub max_tier: usize,
     pub is_generic: bool,
     pub life_stage: usize, // NEW: Which life stage this event belongs to
+    /// Embedded icon name or PNG path shown in the modal header
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
 
     // Procedural generation metadata
     #[serde(default)]
@@ -57,46 +132,796 @@ pub struct EventData {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub procedural_domain: Option<String>,
+    /// Labelled fragments of a procedural description; empty for handcrafted events
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub description_parts: Vec<DescriptionPart>,
+
+    /// Author notes carried through the CSV round trip; never used in play
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+}
+
+/// Which pipeline produced an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSource {
+    Procedural(String), // situation id
+    Handcrafted,
+}
+
+/// Which pipelines `generate_event` may draw from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenerationMode {
+    /// Procedural first, handcrafted once every attempt has failed
+    #[default]
+    Mixed,
+    /// Handcrafted events only, for testing CSV content on its own
+    HandcraftedOnly,
+    /// Procedural events only; a failed generation is reported instead of
+    /// being covered by a handcrafted event
+    ProceduralOnly,
+}
+
+impl GenerationMode {
+    pub const ALL: [GenerationMode; 3] = [
+        GenerationMode::Mixed,
+        GenerationMode::HandcraftedOnly,
+        GenerationMode::ProceduralOnly,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GenerationMode::Mixed => "Procedural, handcrafted fallback",
+            GenerationMode::HandcraftedOnly => "Handcrafted only",
+            GenerationMode::ProceduralOnly => "Procedural only",
+        }
+    }
+
+    /// Reads `--no-procedural` or `--procedural-only` from the command line
+    pub fn from_args(args: &[String]) -> anyhow::Result<Self> {
+        let flag = |name: &str| args.iter().any(|arg| arg == name);
+        match (flag("--no-procedural"), flag("--procedural-only")) {
+            (true, true) => {
+                anyhow::bail!("--no-procedural and --procedural-only can't be used together")
+            }
+            (true, false) => Ok(GenerationMode::HandcraftedOnly),
+            (false, true) => Ok(GenerationMode::ProceduralOnly),
+            (false, false) => Ok(GenerationMode::Mixed),
+        }
+    }
+}
+
+impl EventData {
+    /// Checks every option; see `EventOption::validate`
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.min_tier <= self.max_tier && self.max_tier < TIER_COUNT,
+            "event '{}' covers tiers {}-{}, but only 0-{} exist",
+            self.title,
+            self.min_tier,
+            self.max_tier,
+            TIER_COUNT - 1
+        );
+        for option in &self.options {
+            option
+                .validate()
+                .map_err(|e| anyhow::anyhow!("event '{}': {}", self.title, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn source(&self) -> EventSource {
+        match &self.procedural_id {
+            Some(id) => EventSource::Procedural(id.clone()),
+            None => EventSource::Handcrafted,
+        }
+    }
+
+    /// The situation's domain for procedural events; `None` for handcrafted
+    /// events or an unrecognised domain string
+    pub fn domain(&self) -> Option<EventDomain> {
+        self.procedural_domain.as_deref()?.parse().ok()
+    }
+
+    /// Whether a player at `tier` may still resolve this event. Procedural
+    /// events carry their situation's range and are placed up to
+    /// `TIER_PLACEMENT_TOLERANCE` tiers outside it. Handcrafted events are
+    /// matched through the event index when drawn, so they always fit.
+    pub fn fits_tier(&self, tier: usize) -> bool {
+        if self.procedural_id.is_none() {
+            return true;
+        }
+        let placement = placement_range(tier);
+        *placement.end() >= self.min_tier && *placement.start() <= self.max_tier
+    }
+
+    /// The option Escape picks, if the config lets it: the only option of a
+    /// single-option event, or the trailing "Do nothing" of any other
+    pub fn escape_option(&self, config: &GameConfig) -> Option<usize> {
+        if self.options.len() == 1 {
+            return config.escape_acknowledges.then_some(0);
+        }
+        let last = self.options.len().checked_sub(1)?;
+        (config.escape_skips && self.options[last].is_skip).then_some(last)
+    }
+
+    /// Short lead-in for the event window: the opening fragment of a
+    /// procedural description, or the whole description otherwise.
+    pub fn summary(&self) -> &str {
+        match self.description_parts.first() {
+            Some(part) if self.description_parts.len() > 1 => &part.text,
+            _ => &self.description,
+        }
+    }
+
+    /// The rest of the description after `summary`, if there is any
+    pub fn details(&self) -> Option<String> {
+        if self.description_parts.len() < 2 {
+            return None;
+        }
+        let rest: Vec<&str> = self.description_parts[1..]
+            .iter()
+            .map(|part| part.text.as_str())
+            .collect();
+        Some(rest.join(" "))
+    }
 }
 
 // --- Main Event Generation Function ---
 
-/// Checks if the player meets the requirements for a specific option.
-fn player_meets_requirements(player_state: &LotusApp, requirements: &HashMap<String, u32>) -> bool {
-    for (key, &required_value) in requirements {
-        let player_value = match key.as_str() {
-            "guanxi_family" => player_state.guanxi_family,
-            "guanxi_network" => player_state.guanxi_network,
-            "guanxi_party" => player_state.guanxi_party,
-            "career_level" => player_state.career_level,
-            _ => 0,
+/// Stands in for an event's options when the player qualifies for none of
+/// them, so the event can still be closed.
+fn continue_option() -> EventOption {
+    EventOption {
+        text: "Continue.".to_string(),
+        requirements: Requirements::new(),
+        risk_chance: 0,
+        success_outcome: EventOutcome::default(),
+        success_result: "There was nothing you could do.".to_string(),
+        failure_outcome: None,
+        failure_result: String::new(),
+        archetype: None,
+        risk_breakdown: None,
+        notes: String::new(),
+        is_skip: false,
+    }
+}
+
+const SKIP_OPTION_TEXT: &str = "Do nothing.";
+
+/// The synthetic "Do nothing" option appended to generated events. It has no
+/// requirements or risk, so it is always available.
+pub fn skip_option(config: &GameConfig) -> EventOption {
+    EventOption {
+        text: SKIP_OPTION_TEXT.to_string(),
+        requirements: Requirements::new(),
+        risk_chance: 0,
+        success_outcome: EventOutcome {
+            scs_change: -config.skip_option_scs_penalty,
+            ..Default::default()
+        },
+        success_result: "You kept out of it. Inaction is noted, too.".to_string(),
+        failure_outcome: None,
+        failure_result: String::new(),
+        archetype: None,
+        risk_breakdown: None,
+        notes: String::new(),
+        is_skip: true,
+    }
+}
+
+/// Builds the scripted event shown when finances hit the configured floor.
+/// Guanxi bailouts restore finances to zero; declaring bankruptcy does the
+/// same at a heavy SCS cost and is always available.
+pub fn bankruptcy_event(player_state: &LotusApp) -> EventData {
+    let restore = EventOutcome {
+        finance_change: 0i32.saturating_sub(player_state.finances).max(0),
+        ..Default::default()
+    };
+    let player_stats = PlayerStats::from(player_state);
+    let bailout =
+        |text: &str, key: RequirementKey, cost: i32, outcome: EventOutcome, result: &str| EventOption {
+            text: text.to_string(),
+            requirements: Requirements::from_iter([(key, cost as u32)]),
+            risk_chance: 0,
+            success_outcome: outcome,
+            success_result: result.to_string(),
+            failure_outcome: None,
+            failure_result: String::new(),
+            archetype: None,
+            risk_breakdown: None,
+            notes: String::new(),
+            is_skip: false,
         };
 
-        if player_value < required_value {
-            return false;
+    let options = vec![
+        bailout(
+            "Beg your family to cover the debt.",
+            RequirementKey::GuanxiFamily,
+            2,
+            EventOutcome {
+                guanxi_family_change: -2,
+                ..restore.clone()
+            },
+            "Your family pays, and reminds you of it at every dinner.",
+        ),
+        bailout(
+            "Call in favors from your network.",
+            RequirementKey::GuanxiNetwork,
+            2,
+            EventOutcome {
+                guanxi_network_change: -2,
+                ..restore.clone()
+            },
+            "Old contacts quietly settle your accounts. You owe them now.",
+        ),
+        bailout(
+            "Ask a Party contact to make it go away.",
+            RequirementKey::GuanxiParty,
+            1,
+            EventOutcome {
+                guanxi_party_change: -1,
+                ..restore.clone()
+            },
+            "The debt disappears from the records. Your contact will remember.",
+        ),
+        EventOption {
+            text: "Declare bankruptcy.".to_string(),
+            requirements: Requirements::new(),
+            risk_chance: 0,
+            success_outcome: EventOutcome {
+                scs_change: -player_state.config.bankruptcy_scs_penalty,
+                ..restore
+            },
+            success_result: "Your debts are written off. Your record is not.".to_string(),
+            failure_outcome: None,
+            failure_result: String::new(),
+            archetype: None,
+            risk_breakdown: None,
+            notes: String::new(),
+            is_skip: false,
+        },
+    ];
+
+    EventData {
+        title: "Bankruptcy".to_string(),
+        description: "Your debts have reached the limit. Creditors, the bank, and the credit bureau all want answers. How will you settle this?".to_string(),
+        options: options
+            .into_iter()
+            .filter(|option| player_stats.meets(&option.requirements))
+            .collect(),
+        min_tier: 0,
+        max_tier: TIER_COUNT - 1,
+        is_generic: true,
+        life_stage: player_state.life_stage,
+        icon: None,
+        procedural_id: None,
+        procedural_domain: None,
+        description_parts: Vec::new(),
+        notes: String::new(),
+    }
+}
+
+/// Builds the one-time warning shown instead of the player's first drop to
+/// Tier D. Guanxi or a payment buys back `scs_needed` SCS; accepting the drop
+/// is always available. Paying is only offered when the player can afford it.
+pub fn grace_event(player_state: &LotusApp, scs_needed: i32) -> EventData {
+    let player_stats = PlayerStats::from(player_state);
+    let cost = player_state.config.grace_finance_cost;
+    let redeem = |text: &str, requirements: Requirements, outcome: EventOutcome, result: &str| {
+        EventOption {
+            text: text.to_string(),
+            requirements,
+            risk_chance: 0,
+            success_outcome: EventOutcome {
+                scs_change: scs_needed,
+                ..outcome
+            },
+            success_result: result.to_string(),
+            failure_outcome: None,
+            failure_result: String::new(),
+            archetype: None,
+            risk_breakdown: None,
+            notes: String::new(),
+            is_skip: false,
+        }
+    };
+
+    let mut options = vec![
+        redeem(
+            "Have your family vouch for you.",
+            Requirements::from_iter([(RequirementKey::GuanxiFamily, 2)]),
+            EventOutcome {
+                guanxi_family_change: -2,
+                ..Default::default()
+            },
+            "Your relatives sign a letter of good character. The bureau relents, for now.",
+        ),
+        redeem(
+            "Get a well-placed contact to review your file.",
+            Requirements::from_iter([(RequirementKey::GuanxiNetwork, 2)]),
+            EventOutcome {
+                guanxi_network_change: -2,
+                ..Default::default()
+            },
+            "A few entries in your file are quietly reclassified.",
+        ),
+    ];
+    if player_state.finances >= cost {
+        options.push(redeem(
+            "Pay for a \"credit repair\" course.",
+            Requirements::new(),
+            EventOutcome {
+                finance_change: -cost,
+                ..Default::default()
+            },
+            "The course is expensive and pointless. The certificate is not.",
+        ));
+    }
+    options.push(EventOption {
+        text: "Accept the downgrade.".to_string(),
+        requirements: Requirements::new(),
+        risk_chance: 0,
+        success_outcome: EventOutcome::default(),
+        success_result: "You let the review take its course.".to_string(),
+        failure_outcome: None,
+        failure_result: String::new(),
+        archetype: None,
+        risk_breakdown: None,
+        notes: String::new(),
+        is_skip: false,
+    });
+
+    EventData {
+        title: "Final Warning".to_string(),
+        description: "A letter from the credit bureau: your score now places you in Tier D, and the reclassification takes effect unless your record improves. There may still be time to fix it, for a price.".to_string(),
+        options: options
+            .into_iter()
+            .filter(|option| player_stats.meets(&option.requirements))
+            .collect(),
+        min_tier: 0,
+        max_tier: TIER_COUNT - 1,
+        is_generic: true,
+        life_stage: player_state.life_stage,
+        icon: Some("warning".to_string()),
+        procedural_id: None,
+        procedural_domain: None,
+        description_parts: Vec::new(),
+        notes: String::new(),
+    }
+}
+
+// --- SCS Review Outcomes ---
+const REVIEW_PROMOTION_SCS: i32 = 30;
+const REVIEW_AUDIT_SCS: i32 = -40;
+const REVIEW_AUDIT_FINANCE: i32 = -200;
+const REVIEW_PROMOTION_FAVORS: i32 = 1;
+
+/// Rolls the SCS review table for a review petal. Positive SCS momentum since
+/// the last review tilts the odds toward a commendation, negative toward an audit.
+pub fn review_event(player_state: &LotusApp, rng: &mut impl Rng) -> EventData {
+    use rand::distr::Distribution;
+    use rand::distr::weighted::WeightedIndex;
+
+    let config = &player_state.config;
+    let momentum = player_state.scs_since_review / config.review_scs_per_weight.max(1);
+    let weights = [
+        config.review_promotion_weight + momentum.max(0) as u32,
+        config.review_neutral_weight,
+        config.review_audit_weight + (-momentum).max(0) as u32,
+    ];
+    log::info!(
+        "SCS review: momentum={} (scs_since_review={}), weights={:?}",
+        momentum,
+        player_state.scs_since_review,
+        weights
+    );
+
+    let roll = WeightedIndex::new(weights)
+        .map(|dist| dist.sample(rng))
+        .unwrap_or(1);
+    let (description, outcome, result) = match roll {
+        0 => (
+            "The review board praises your exemplary conduct. A commendation is added to your file.",
+            EventOutcome {
+                scs_change: REVIEW_PROMOTION_SCS,
+                favor_change: REVIEW_PROMOTION_FAVORS,
+                ..Default::default()
+            },
+            "You were commended at your SCS review. Someone owes you a favor.",
+        ),
+        2 => (
+            "The review board flags irregularities in your record and orders an audit.",
+            EventOutcome {
+                scs_change: REVIEW_AUDIT_SCS,
+                finance_change: REVIEW_AUDIT_FINANCE,
+                ..Default::default()
+            },
+            "The audit cost you dearly.",
+        ),
+        _ => (
+            "The review board reads your file without comment and moves on to the next citizen.",
+            EventOutcome::default(),
+            "Your SCS review passed without incident.",
+        ),
+    };
+
+    EventData {
+        title: "SCS Review".to_string(),
+        description: description.to_string(),
+        options: vec![EventOption {
+            text: "Acknowledge".to_string(),
+            requirements: Requirements::new(),
+            risk_chance: 0,
+            success_outcome: outcome,
+            success_result: result.to_string(),
+            failure_outcome: None,
+            failure_result: String::new(),
+            archetype: None,
+            risk_breakdown: None,
+            notes: String::new(),
+            is_skip: false,
+        }],
+        min_tier: 0,
+        max_tier: TIER_COUNT - 1,
+        is_generic: true,
+        life_stage: player_state.life_stage,
+        icon: Some("review_stamp".to_string()),
+        procedural_id: None,
+        procedural_domain: None,
+        description_parts: Vec::new(),
+        notes: String::new(),
+    }
+}
+
+/// `events.json` in the source tree, read at runtime without the
+/// `embedded-events` feature
+pub const EVENTS_JSON_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/events.json");
+
+/// Where `load_event_database` reads from in this build, for the startup log
+pub fn events_source() -> &'static str {
+    if cfg!(feature = "embedded-events") {
+        "embedded at build time"
+    } else {
+        EVENTS_JSON_PATH
+    }
+}
+
+/// Parses the handcrafted event database embedded at compile time
+#[cfg(feature = "embedded-events")]
+pub fn load_event_database() -> anyhow::Result<Vec<EventData>> {
+    let event_json = include_str!("events.json");
+    parse_event_database(event_json)
+}
+
+/// Parses the handcrafted event database from the source tree, so a
+/// `--convert` shows up without rebuilding
+#[cfg(not(feature = "embedded-events"))]
+pub fn load_event_database() -> anyhow::Result<Vec<EventData>> {
+    load_event_database_from(std::path::Path::new(EVENTS_JSON_PATH))
+}
+
+/// Parses a handcrafted event database from a file, such as a content pack's
+pub fn load_event_database_from(path: &std::path::Path) -> anyhow::Result<Vec<EventData>> {
+    use anyhow::Context;
+    let event_json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_event_database(&event_json)
+}
+
+/// Migrations for `events.json`; see `schema`
+const EVENT_MIGRATIONS: [Migration; 1] = [
+    // 0 -> 1: the bare event array moved into a versioned object
+    |document| {
+        if document.is_array() {
+            *document = serde_json::json!({ "events": document.take() });
+        }
+        Ok(())
+    },
+];
+/// Schema version `events.json` is written with
+pub const EVENTS_VERSION: u32 = EVENT_MIGRATIONS.len() as u32;
+
+/// Top-level layout of `events.json`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventFile {
+    pub version: u32,
+    pub events: Vec<EventData>,
+}
+
+/// Parses an events document of any known version, upgrading older ones
+pub fn parse_event_database(json: &str) -> anyhow::Result<Vec<EventData>> {
+    let mut document: serde_json::Value = serde_json::from_str(json)?;
+    schema::upgrade(&mut document, &EVENT_MIGRATIONS, "events.json")?;
+    let file: EventFile = serde_json::from_value(document)?;
+    for event in &file.events {
+        event.validate()?;
+    }
+    Ok(file.events)
+}
+
+/// Pre-computes the (life_stage, tier) -> (tier_specific, generic) event index
+pub fn build_event_index(
+    event_database: &[EventData],
+) -> HashMap<(usize, usize), (Vec<usize>, Vec<usize>)> {
+    let mut event_index = HashMap::new();
+    for (i, event) in event_database.iter().enumerate() {
+        for tier in event.min_tier..=event.max_tier {
+            let (tier_specific, generic) = event_index
+                .entry((event.life_stage, tier))
+                .or_insert_with(|| (Vec::new(), Vec::new()));
+            if event.is_generic {
+                generic.push(i);
+            } else {
+                tier_specific.push(i);
+            }
         }
     }
-    true
+    event_index
+}
+
+/// Why no event could be produced for the player's position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationError {
+    NoEventsFound { tier: usize, life_stage: usize },
+    /// Procedural-only mode ran out of attempts
+    NoProceduralContent { tier: usize, life_stage: usize, attempts: u32 },
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::NoEventsFound { tier, life_stage } => write!(
+                f,
+                "no events found for player tier {} and life stage {}",
+                tier, life_stage
+            ),
+            GenerationError::NoProceduralContent { tier, life_stage, attempts } => write!(
+                f,
+                "no procedural content available for player tier {} and life stage {} \
+                 after {} attempt(s)",
+                tier, life_stage, attempts
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+/// How `generate_event` arrived at its event, for the developer tools
+#[derive(Debug, Clone, Default)]
+pub struct GenerationTrace {
+    /// Procedural attempts in order; the last one succeeded unless `handcrafted`
+    pub attempts: Vec<AttemptTrace>,
+    /// Every attempt failed and a handcrafted event was drawn instead
+    pub handcrafted: bool,
 }
 
 /// This function is called by app.rs to get a new event.
-/// It first attempts procedural generation, then falls back to handcrafted events.
-pub fn generate_event(player_state: &LotusApp) -> EventData {
-    use rand::prelude::IndexedRandom;
-    let mut rng = rand::rng();
-    let current_tier = player_state.player_tier;
-    let current_stage = player_state.life_stage;
+/// It first attempts procedural generation, then falls back to handcrafted events,
+/// unless the app's `GenerationMode` restricts it to one of the two.
+pub fn generate_event(player_state: &LotusApp) -> Result<EventData, GenerationError> {
+    generate_event_with_rng(player_state, &mut *player_state.rng.borrow_mut())
+}
+
+/// Same as `generate_event`, drawing all randomness from the given RNG
+pub fn generate_event_with_rng(
+    player_state: &LotusApp,
+    rng: &mut impl Rng,
+) -> Result<EventData, GenerationError> {
+    generate_event_traced(player_state, rng).0
+}
+
+/// Same as `generate_event_with_rng`, also returning how the event was chosen
+pub fn generate_event_traced(
+    player_state: &LotusApp,
+    rng: &mut impl Rng,
+) -> (Result<EventData, GenerationError>, GenerationTrace) {
+    let position = (player_state.player_tier, player_state.life_stage);
+    generate_event_at(player_state, position, rng)
+}
+
+/// Same as `generate_event_traced`, for the player standing at
+/// `(tier, life_stage)` instead of where they are
+fn generate_event_at(
+    player_state: &LotusApp,
+    (tier, life_stage): (usize, usize),
+    rng: &mut impl Rng,
+) -> (Result<EventData, GenerationError>, GenerationTrace) {
+    let mut trace = GenerationTrace::default();
+    if player_state.generation_mode == GenerationMode::HandcraftedOnly {
+        log::debug!("Procedural generation is off; drawing a handcrafted event");
+        trace.handcrafted = true;
+        return (generate_handcrafted_event(player_state, (tier, life_stage), rng), trace);
+    }
+
+    // Attempt procedural generation first. Each attempt rolls its wildcard
+    // and situation afresh from the same RNG, so retries stay reproducible.
+    // The last attempt relaxes the option minimum to a single choice.
+    let attempts = player_state.config.procedural_attempts.max(1);
+    for attempt in 1..=attempts {
+        let min_options = if attempt < attempts {
+            player_state.config.min_options
+        } else {
+            1
+        };
+        let mut attempt_trace = AttemptTrace::default();
+        let event = procedural::generate_procedural_event(
+            player_state,
+            tier,
+            life_stage,
+            rng,
+            min_options,
+            &mut attempt_trace,
+        );
+        trace.attempts.push(attempt_trace);
+        if let Some(procedural_event) = event {
+            if attempt > 1 {
+                log::info!("Procedural generation succeeded on attempt {}/{}", attempt, attempts);
+            }
+            return (Ok(procedural_event), trace);
+        }
+    }
 
-    // Attempt procedural generation first
-    if let Some(procedural_event) = procedural::generate_procedural_event(player_state, &mut rng) {
-        return procedural_event;
+    if player_state.generation_mode == GenerationMode::ProceduralOnly {
+        log::error!(
+            "Procedural-only mode: no situation generated after {} attempt(s)",
+            attempts
+        );
+        let error = GenerationError::NoProceduralContent {
+            tier,
+            life_stage,
+            attempts,
+        };
+        return (Err(error), trace);
     }
 
-    // Fallback to handcrafted events
     log::info!("=== FALLING BACK TO HANDCRAFTED EVENTS ===");
-    log::info!("  Reason: Procedural generation returned None");
+    log::info!("  Reason: Procedural generation returned None {} time(s)", attempts);
+    trace.handcrafted = true;
+    (generate_handcrafted_event(player_state, (tier, life_stage), rng), trace)
+}
+
+/// Tally key for a handcrafted event, which has no situation id
+pub const HANDCRAFTED_PREFIX: &str = "handcrafted: ";
+/// Tally key for draws that produced no event
+pub const NO_EVENT_KEY: &str = "(no event)";
+
+/// Runs the full generation pipeline `samples` times for a player standing at
+/// `tier` and `stage`, counting the events drawn by situation id (handcrafted
+/// events by `HANDCRAFTED_PREFIX` and title). The player stays where they are
+/// and a fresh RNG is seeded from `seed`, so the game itself is untouched and
+/// the same seed gives the same tally.
+pub fn preview_event_distribution(
+    player_state: &LotusApp,
+    tier: usize,
+    stage: usize,
+    samples: usize,
+    seed: u64,
+) -> HashMap<String, usize> {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let mut counts = HashMap::new();
+    for _ in 0..samples {
+        let key = match generate_event_at(player_state, (tier, stage), &mut rng).0 {
+            Ok(event) => event
+                .procedural_id
+                .unwrap_or_else(|| format!("{}{}", HANDCRAFTED_PREFIX, event.title)),
+            Err(_) => NO_EVENT_KEY.to_string(),
+        };
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Draws a handcrafted event for `(tier, life_stage)` once procedural
+/// generation has given up or is off
+fn generate_handcrafted_event(
+    player_state: &LotusApp,
+    (current_tier, current_stage): (usize, usize),
+    rng: &mut impl Rng,
+) -> Result<EventData, GenerationError> {
+    use rand::prelude::SliceRandom;
+
     log::info!("  Player state: tier={}, life_stage={}", current_tier, current_stage);
 
+    // Filter the chosen template's options by player state. A template the
+    // player can't act on at all is set aside and another one drawn, so an
+    // event with no options never reaches the UI. If every template is set
+    // aside, the last one is shown with a plain "Continue" instead.
+    let player_stats = PlayerStats::from(player_state);
+    let mut rejected = HashSet::new();
+    let mut last_rejected: Option<usize> = None;
+    let (chosen_event_template, mut available_options) = loop {
+        let position = (current_tier, current_stage);
+        let Some(event_index) = choose_handcrafted_event(player_state, position, &rejected, rng)
+        else {
+            let Some(index) = last_rejected else {
+                return Err(GenerationError::NoEventsFound {
+                    tier: current_tier,
+                    life_stage: current_stage,
+                });
+            };
+            let template = &player_state.event_database[index];
+            log::warn!("  No event has options the player qualifies for; '{}' gets Continue", template.title);
+            return Ok(handcrafted_event(template, vec![continue_option()]));
+        };
+        let template = &player_state.event_database[event_index];
+        let options: Vec<EventOption> = template
+            .options
+            .iter()
+            .filter(|option| player_stats.meets(&option.requirements))
+            .cloned()
+            .collect();
+        if !options.is_empty() {
+            break (template, options);
+        }
+        log::warn!("  '{}' has no options the player qualifies for; drawing again", template.title);
+        rejected.insert(event_index);
+        last_rejected = Some(event_index);
+    };
+
+    if player_state.config.shuffle_options {
+        available_options.shuffle(rng);
+    }
+    if player_state.config.skip_option_enabled {
+        available_options.push(skip_option(&player_state.config));
+    }
+
+    // Return the final event with only the available options
+    Ok(handcrafted_event(chosen_event_template, available_options))
+}
+
+/// A handcrafted template as presented, with the options the player may take
+fn handcrafted_event(template: &EventData, options: Vec<EventOption>) -> EventData {
+    // Author notes stay in the data files
+    let options = options
+        .into_iter()
+        .map(|option| EventOption {
+            notes: String::new(),
+            ..option
+        })
+        .collect();
+    EventData {
+        title: template.title.clone(),
+        description: template.description.clone(),
+        options,
+        min_tier: 0,
+        max_tier: 0,
+        is_generic: false,
+        life_stage: template.life_stage,
+        icon: template.icon.clone(),
+        procedural_id: None,
+        procedural_domain: None,
+        description_parts: Vec::new(),
+        notes: String::new(),
+    }
+}
+
+/// Draws one of `candidates`, favoring events the player hasn't seen lately
+fn choose_by_recency(
+    player_state: &LotusApp,
+    candidates: &[usize],
+    rng: &mut impl Rng,
+) -> Option<usize> {
+    use rand::distr::weighted::WeightedIndex;
+    let weights = candidates
+        .iter()
+        .map(|&i| player_state.handcrafted_weight(&player_state.event_database[i].title));
+    let dist = WeightedIndex::new(weights).ok()?;
+    Some(candidates[rng.sample(dist)])
+}
+
+/// Picks a handcrafted event for `(tier, life_stage)`, preferring
+/// tier-specific events, then generic ones, then generic events from earlier
+/// life stages. Events in `rejected` are skipped.
+fn choose_handcrafted_event(
+    player_state: &LotusApp,
+    (current_tier, current_stage): (usize, usize),
+    rejected: &HashSet<usize>,
+    rng: &mut impl Rng,
+) -> Option<usize> {
     let mut potential_events: Vec<usize> = Vec::new();
 
     // 1. Try to find a non-generic (tier-specific) event for the current stage
@@ -105,11 +930,10 @@ pub fn generate_event(player_state: &LotusApp) -> EventData {
         potential_events.extend(tier_specific);
     }
 
-    let chosen_event_template: &EventData = if let Some(&event_index) =
-        potential_events.choose(&mut rng)
-    {
+    potential_events.retain(|i| !rejected.contains(i));
+    if let Some(event_index) = choose_by_recency(player_state, &potential_events, rng) {
         log::info!("✓ Selected tier-specific handcrafted event: '{}'", player_state.event_database[event_index].title);
-        &player_state.event_database[event_index]
+        Some(event_index)
     } else {
         // 2. If none, find a generic event for the current stage
         log::debug!("  No tier-specific events, trying generic events");
@@ -117,9 +941,10 @@ pub fn generate_event(player_state: &LotusApp) -> EventData {
             log::debug!("  Found {} generic events for stage={}, tier={}", generic.len(), current_stage, current_tier);
             potential_events.extend(generic);
         }
-        if let Some(&event_index) = potential_events.choose(&mut rng) {
+        potential_events.retain(|i| !rejected.contains(i));
+        if let Some(event_index) = choose_by_recency(player_state, &potential_events, rng) {
             log::info!("✓ Selected generic handcrafted event: '{}'", player_state.event_database[event_index].title);
-            &player_state.event_database[event_index]
+            Some(event_index)
         } else {
             // 3. Fallback: find *any* generic event from a past life stage
             log::debug!("  No generic events for current stage, trying past life stages");
@@ -129,63 +954,430 @@ pub fn generate_event(player_state: &LotusApp) -> EventData {
                     potential_events.extend(generic);
                 }
             }
-            if let Some(&event_index) = potential_events.choose(&mut rng) {
+            potential_events.retain(|i| !rejected.contains(i));
+            if let Some(event_index) = choose_by_recency(player_state, &potential_events, rng) {
                 log::info!("✓ Selected past life stage handcrafted event: '{}'", player_state.event_database[event_index].title);
-                &player_state.event_database[event_index]
+                Some(event_index)
             } else {
-                // 4. Absolute fallback
+                // Absolute fallback
                 log::error!("!!! NO EVENTS FOUND !!!");
                 log::error!("  No handcrafted events available for tier={}, life_stage={}", current_tier, current_stage);
-                log::error!("  Returning error event");
-                return EventData {
-                    title: "No Event Found!".to_string(),
-                    description: format!(
-                        "Error: No events found for player tier {} and life stage {}. Please check events.json.",
-                        player_state.player_tier, player_state.life_stage
-                    ),
-                    options: vec![EventOption {
-                        text: "Continue".to_string(),
-                        requirements: Default::default(),
-                        risk_chance: 0,
-                        success_outcome: Default::default(),
-                        success_result: "".to_string(),
-                        failure_outcome: None,
-                        failure_result: "".to_string(),
-                    }],
-                    min_tier: 0,
-                    max_tier: 99,
-                    is_generic: true,
-                    life_stage: 0,
-                    procedural_id: None,
-                    procedural_domain: None,
-                };
+                None
             }
         }
-    };
+    }
+}
 
-    // 4. We have an event template. Now, filter its options based on player state.
-    let available_options: Vec<EventOption> = chosen_event_template
-        .options
-        .iter()
-        .filter_map(|option| {
-            if player_meets_requirements(player_state, &option.requirements) {
-                Some(option.clone())
-            } else {
-                None
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{Fixture, TEST_SEED};
+    use crate::game_rng::GameRng;
+    use crate::procedural::SituationLibrary;
+    use crate::procedural::library::Severity;
+    use anyhow::Result;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::cell::RefCell;
+
+    // Several draws per position to cover wildcard and weighting rolls
+    const POSITION_SAMPLES: usize = 25;
+
+    fn option(text: &str, requirements: Requirements, risk_chance: u8) -> EventOption {
+        EventOption {
+            text: text.to_string(),
+            requirements,
+            risk_chance,
+            success_outcome: EventOutcome::default(),
+            success_result: String::new(),
+            failure_outcome: Some(EventOutcome::default()),
+            failure_result: "It went wrong.".to_string(),
+            archetype: None,
+            risk_breakdown: None,
+            notes: String::new(),
+            is_skip: false,
+        }
+    }
+
+    /// A certain failure loads, anything above it doesn't
+    #[test]
+    fn risk_chance_is_a_percentage() {
+        let requirements = Requirements::default();
+        assert!(option("certain", requirements.clone(), MAX_RISK_CHANCE).validate().is_ok());
+        assert!(option("over", requirements, MAX_RISK_CHANCE + 1).validate().is_err());
+    }
+
+    #[test]
+    fn versionless_events_upgrade() -> Result<()> {
+        let events = load_event_database()?;
+        let bare_array = serde_json::to_string(&events)?;
+        assert_eq!(parse_event_database(&bare_array)?.len(), events.len());
+        Ok(())
+    }
+
+    /// Every life stage and tier draws a valid event with an option to take,
+    /// from a situation that reaches the board there
+    #[test]
+    fn every_position_gets_an_event_that_fits() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        for life_stage in 1..=app.config.life_stage_count() {
+            for tier in 0..TIER_COUNT {
+                app.life_stage = life_stage;
+                app.player_tier = tier;
+                let mut rng = StdRng::seed_from_u64(TEST_SEED);
+                for sample in 0..POSITION_SAMPLES {
+                    let position = format!("stage {} tier {} sample {}", life_stage, tier, sample);
+                    let event = generate_event_with_rng(&app, &mut rng)
+                        .map_err(|e| anyhow::anyhow!("{}: {}", position, e))?;
+                    event.validate()?;
+                    assert!(!event.options.is_empty(), "'{}' at {}", event.title, position);
+                    assert!(event.fits_tier(tier), "'{}' at {}", event.title, position);
+                }
             }
-        })
-        .collect();
+        }
+        Ok(())
+    }
 
-    // 5. Return the final event with only the available options
-    EventData {
-        title: chosen_event_template.title.clone(),
-        description: chosen_event_template.description.clone(),
-        options: available_options,
-        min_tier: 0,
-        max_tier: 0,
-        is_generic: false,
-        life_stage: chosen_event_template.life_stage,
-        procedural_id: None,
-        procedural_domain: None,
+    /// A player with no career or guanxi to speak of qualifies for few
+    /// choices, yet their procedural events still offer `min_options`
+    /// authored choices, except from the last attempt, which may settle for one
+    #[test]
+    fn weak_player_still_gets_min_options() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.career_level = 0;
+        app.guanxi_family = 0;
+        app.guanxi_network = 0;
+        app.guanxi_party = 0;
+        let attempts = app.config.procedural_attempts.max(1) as usize;
+        let min_options = app.config.min_options;
+        assert!(min_options > 1, "min_options {} checks nothing", min_options);
+
+        let mut rejections = 0;
+        for life_stage in 1..=app.config.life_stage_count() {
+            for tier in 0..TIER_COUNT {
+                app.life_stage = life_stage;
+                app.player_tier = tier;
+                let mut rng = StdRng::seed_from_u64(TEST_SEED);
+                for _ in 0..POSITION_SAMPLES {
+                    let (result, trace) = generate_event_traced(&app, &mut rng);
+                    rejections += trace
+                        .attempts
+                        .iter()
+                        .filter(|attempt| attempt.selected.is_some() && attempt.failure.is_some())
+                        .count();
+                    let Ok(event) = result else {
+                        continue;
+                    };
+                    if event.procedural_id.is_some() && trace.attempts.len() < attempts {
+                        let authored =
+                            event.options.iter().filter(|o| o.archetype.is_some()).count();
+                        assert!(authored >= min_options, "'{}' offers {}", event.title, authored);
+                    }
+                }
+            }
+        }
+        assert!(rejections > 0, "a weak player never had a situation rejected");
+        Ok(())
+    }
+
+    /// Filter counts add up, a procedural event names the situation its last
+    /// attempt selected, and a handcrafted one comes only after every
+    /// attempt failed
+    #[test]
+    fn traces_agree_with_their_events() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        let attempts = app.config.procedural_attempts.max(1) as usize;
+        for life_stage in 1..=app.config.life_stage_count() {
+            for tier in 0..TIER_COUNT {
+                app.life_stage = life_stage;
+                app.player_tier = tier;
+                let mut rng = StdRng::seed_from_u64(TEST_SEED);
+                for _ in 0..POSITION_SAMPLES {
+                    let (result, trace) = generate_event_traced(&app, &mut rng);
+                    let Ok(event) = result else {
+                        continue;
+                    };
+                    for attempt in &trace.attempts {
+                        let counts = &attempt.filtered;
+                        let filtered = counts.draft
+                            + counts.tier
+                            + counts.life_stage
+                            + counts.encountered
+                            + counts.recent_domain
+                            + counts.candidates;
+                        assert_eq!(filtered, counts.total, "{}", attempt.summary());
+                    }
+                    let consistent = match &event.procedural_id {
+                        Some(id) => {
+                            !trace.handcrafted
+                                && trace.attempts.last().is_some_and(|attempt| {
+                                    attempt.selected.as_ref() == Some(id)
+                                        && attempt.failure.is_none()
+                                })
+                        }
+                        None => {
+                            trace.handcrafted
+                                && trace.attempts.len() == attempts
+                                && trace.attempts.iter().all(|attempt| attempt.failure.is_some())
+                        }
+                    };
+                    assert!(consistent, "'{}' doesn't match {:?}", event.title, trace);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn handcrafted_only_skips_procedural_attempts() -> Result<()> {
+        let mut app =
+            Fixture::new()?.build()?.with_generation_mode(GenerationMode::HandcraftedOnly);
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        let mut drawn = 0;
+        for sample in 0..POSITION_SAMPLES {
+            app.player_tier = sample % TIER_COUNT;
+            let (event, trace) = generate_event_traced(&app, &mut rng);
+            assert!(trace.handcrafted && trace.attempts.is_empty(), "{:?}", trace);
+            if let Ok(event) = event {
+                assert_eq!(event.procedural_id, None);
+                drawn += 1;
+            }
+        }
+        assert!(drawn > 0, "no handcrafted event was drawn");
+        Ok(())
+    }
+
+    /// An empty library reports the gap after its retries rather than fall
+    /// back to a handcrafted event
+    #[test]
+    fn procedural_only_reports_missing_content() -> Result<()> {
+        let variables = SituationLibrary::from_embedded_configs()?.variables;
+        let empty = SituationLibrary::new(Vec::new(), variables)?;
+        let app = Fixture::embedded(empty)?
+            .build()?
+            .with_generation_mode(GenerationMode::ProceduralOnly);
+        let (event, trace) = generate_event_traced(&app, &mut StdRng::seed_from_u64(TEST_SEED));
+        let attempts = app.config.procedural_attempts.max(1);
+        assert!(
+            matches!(event, Err(GenerationError::NoProceduralContent { attempts: made, .. })
+                if made == attempts),
+            "{:?}",
+            event
+        );
+        assert!(!trace.handcrafted);
+        assert_eq!(trace.attempts.len(), attempts as usize);
+        Ok(())
+    }
+
+    /// A preview is reproducible, counts every draw and deals only situations
+    /// placeable at the position, the tier tolerance and previous stage
+    /// included
+    #[test]
+    fn distribution_preview_counts_placeable_draws() -> Result<()> {
+        const SAMPLES: usize = 200;
+        let (tier, stage) = (2, 3);
+        let app = Fixture::new()?.build()?;
+        let counts = preview_event_distribution(&app, tier, stage, SAMPLES, TEST_SEED);
+        assert_eq!(counts, preview_event_distribution(&app, tier, stage, SAMPLES, TEST_SEED));
+        assert_eq!(counts.values().sum::<usize>(), SAMPLES);
+        let placement = placement_range(tier);
+        for id in counts.keys() {
+            if id.starts_with(HANDCRAFTED_PREFIX) || id == NO_EVENT_KEY {
+                continue;
+            }
+            let situation = app.situation_library.situation_by_id(id).expect("unknown id");
+            assert!(situation.tier_min <= *placement.end(), "{}", id);
+            assert!(situation.tier_max >= *placement.start(), "{}", id);
+            assert!(situation.life_stage_min <= stage, "{}", id);
+            assert!(situation.life_stage_max + 1 >= stage, "{}", id);
+        }
+        Ok(())
+    }
+
+    /// Recency-weighted draws repeat an event back to back less often than
+    /// even draws, at the position with the most events to pick from
+    #[test]
+    fn recent_handcrafted_events_repeat_less() -> Result<()> {
+        const DRAWS: usize = 200;
+        let back_to_back = |window: u64| -> Result<usize> {
+            let mut config = GameConfig::from_embedded()?;
+            config.handcrafted_recency_window = window;
+            let mut app = Fixture { config, ..Fixture::new()? }
+                .build()?
+                .with_generation_mode(GenerationMode::HandcraftedOnly);
+            let busiest = app
+                .event_index
+                .iter()
+                .max_by_key(|(position, (specific, _))| (specific.len(), **position))
+                .map(|(&position, _)| position);
+            if let Some((stage, tier)) = busiest {
+                app.life_stage = stage;
+                app.player_tier = tier;
+            }
+            let mut rng = StdRng::seed_from_u64(TEST_SEED);
+            let mut previous = None;
+            let mut repeats = 0;
+            for _ in 0..DRAWS {
+                let title = generate_event_with_rng(&app, &mut rng)?.title;
+                if previous.as_ref() == Some(&title) {
+                    repeats += 1;
+                }
+                app.record_handcrafted(&title);
+                app.turn += 1;
+                previous = Some(title);
+            }
+            Ok(repeats)
+        };
+        let even = back_to_back(0)?;
+        let spaced = back_to_back(GameConfig::from_embedded()?.handcrafted_recency_window)?;
+        assert!(spaced < even && spaced * 2 <= even, "{} repeats, {} when even", spaced, even);
+        Ok(())
+    }
+
+    /// Escape acknowledges single-option events and picks "Do nothing" only
+    /// when the config allows it
+    #[test]
+    fn escape_picks_only_what_the_config_allows() -> Result<()> {
+        let mut config = GameConfig::from_embedded()?;
+        config.skip_option_enabled = true;
+        let app = Fixture { config: config.clone(), ..Fixture::new()? }.build()?;
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        let procedural = (0..POSITION_SAMPLES)
+            .filter_map(|_| generate_event_with_rng(&app, &mut rng).ok())
+            .find(|event| event.procedural_id.is_some() && event.options.len() > 1)
+            .expect("no procedural event to check");
+        let app = app.with_generation_mode(GenerationMode::HandcraftedOnly);
+        let handcrafted = (0..POSITION_SAMPLES)
+            .filter_map(|_| generate_event_with_rng(&app, &mut rng).ok())
+            .find(|event| event.options.len() > 1)
+            .expect("no handcrafted event to check");
+
+        for mut event in [procedural, handcrafted] {
+            let skip = event.options.len() - 1;
+            config.escape_skips = false;
+            assert_eq!(event.escape_option(&config), None);
+            config.escape_skips = true;
+            assert_eq!(event.escape_option(&config), Some(skip), "{}", event.title);
+            // Without "Do nothing" Escape never picks an authored choice
+            event.options.truncate(skip);
+            if event.options.len() > 1 {
+                assert_eq!(event.escape_option(&config), None);
+            }
+
+            event.options.truncate(1);
+            config.escape_acknowledges = true;
+            assert_eq!(event.escape_option(&config), Some(0));
+            config.escape_acknowledges = false;
+            assert_eq!(event.escape_option(&config), None);
+        }
+        Ok(())
+    }
+
+    /// A handcrafted event whose only option needs unattainable guanxi still
+    /// reaches the player with an option they can take
+    #[test]
+    fn locked_event_is_not_a_soft_lock() -> Result<()> {
+        // No situations, so generation falls back to the handcrafted event
+        let variables = SituationLibrary::from_embedded_configs()?.variables;
+        let library = SituationLibrary::new(Vec::new(), variables)?;
+        let requirements = [(RequirementKey::GuanxiParty, u32::MAX)].into_iter().collect();
+        let locked_event = EventData {
+            title: "Locked Door".to_string(),
+            description: "Only the very well connected get through.".to_string(),
+            options: vec![option("Call the Politburo", requirements, 0)],
+            min_tier: 0,
+            max_tier: TIER_COUNT - 1,
+            is_generic: true,
+            life_stage: 1,
+            icon: None,
+            procedural_id: None,
+            procedural_domain: None,
+            description_parts: Vec::new(),
+            notes: String::new(),
+        };
+        let mut config = GameConfig::from_embedded()?;
+        config.skip_option_enabled = false;
+        let mut app = Fixture {
+            config,
+            events: vec![locked_event],
+            ..Fixture::embedded(library)?
+        }
+        .build()?;
+        app.life_stage = 1;
+        let player_stats = PlayerStats::from(&app);
+        let event = generate_event(&app)?;
+        assert!(event.options.iter().any(|o| player_stats.meets(&o.requirements)));
+        Ok(())
+    }
+
+    /// Over a long run, High severity situations are separated by at least
+    /// the configured number of other events
+    #[test]
+    fn high_severity_events_are_spaced() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.rng = RefCell::new(GameRng::seeded(TEST_SEED));
+        let spacing = app.config.high_severity_spacing;
+        let mut last_high: Option<u64> = None;
+        let mut highs = 0;
+        // Long enough for situations to come back off their encounter cooldown
+        for _ in 0..200 {
+            let event = generate_event(&app).ok();
+            app.turn += 1;
+            let Some(id) = event.and_then(|event| event.procedural_id) else {
+                continue;
+            };
+            let situation = app.situation_library.situation_by_id(&id).expect("unknown id");
+            let domain = situation.domain;
+            if situation.severity == Severity::High {
+                if let Some(last) = last_high {
+                    assert!(app.turn - last > spacing, "'{}' at turn {}", id, app.turn);
+                }
+                last_high = Some(app.turn);
+                highs += 1;
+            }
+            app.update_event_context(domain, id);
+        }
+        assert!(highs > 0, "no High severity event");
+        Ok(())
+    }
+
+    /// Nothing in generation reaches for randomness outside the game's RNG
+    #[test]
+    fn counting_rng_reproduces_events() -> Result<()> {
+        let game = || -> Result<LotusApp> {
+            let mut app = Fixture::new()?.build()?;
+            app.rng = RefCell::new(GameRng::counting());
+            Ok(app)
+        };
+        let (first, second) = (game()?, game()?);
+        let describe = |app: &LotusApp| {
+            generate_event(app).ok().map(|event| (event.title, event.description))
+        };
+        for draw in 0..POSITION_SAMPLES {
+            assert_eq!(describe(&first), describe(&second), "draw {}", draw);
+        }
+        Ok(())
+    }
+
+    /// Games from the same seed see the same events and the same roll results
+    #[test]
+    fn seeded_games_replay() -> Result<()> {
+        let play = || -> Result<Vec<String>> {
+            let mut app = Fixture::new()?.build()?;
+            app.rng = RefCell::new(GameRng::seeded(TEST_SEED));
+            let mut log = Vec::new();
+            for _ in 0..POSITION_SAMPLES {
+                let Ok(event) = generate_event(&app) else {
+                    log.push("no event".to_string());
+                    continue;
+                };
+                let risk_chance = event.options.first().map_or(0, |option| option.risk_chance);
+                let failed = app.roll_failure(risk_chance);
+                log.push(format!("{} / {} / failed: {}", event.title, event.description, failed));
+            }
+            Ok(log)
+        };
+        assert_eq!(play()?, play()?);
+        Ok(())
     }
 }
@@ -0,0 +1,78 @@
+//! Images shown in the event modal header. An event's `icon` names one of the
+//! embedded icons below or a PNG file on disk; each is decoded once and its
+//! texture cached by name.
+
+use anyhow::{Context, Result};
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+
+/// Icons built into the binary, by the name events use for them
+const EMBEDDED_ICONS: [(&str, &[u8]); 3] = [
+    ("party_seal", include_bytes!("../data/icons/party_seal.png")),
+    ("review_stamp", include_bytes!("../data/icons/review_stamp.png")),
+    ("warning", include_bytes!("../data/icons/warning.png")),
+];
+
+/// Side of the square an icon is drawn in, in points
+pub const ICON_SIZE: f32 = 48.0;
+
+/// Names of the icons built into the binary
+pub fn embedded_icons() -> impl Iterator<Item = &'static str> {
+    EMBEDDED_ICONS.iter().map(|(name, _)| *name)
+}
+
+/// The raw PNG for an embedded icon name, or else the file at that path
+fn icon_bytes(name: &str) -> Result<Cow<'static, [u8]>> {
+    if let Some((_, bytes)) = EMBEDDED_ICONS.iter().find(|(icon, _)| *icon == name) {
+        return Ok(Cow::Borrowed(bytes));
+    }
+    let bytes = fs::read(name).with_context(|| format!("no embedded icon or file '{}'", name))?;
+    Ok(Cow::Owned(bytes))
+}
+
+/// Decodes a named icon into an image egui can upload
+pub fn load_icon(name: &str) -> Result<ColorImage> {
+    let bytes = icon_bytes(name)?;
+    let image = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+        .with_context(|| format!("icon '{}' is not a readable PNG", name))?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
+
+/// Textures by icon name. A failed load is remembered as `None`, so a broken
+/// icon is reported once rather than retried every frame.
+#[derive(Default)]
+pub struct IconCache {
+    textures: HashMap<String, Option<TextureHandle>>,
+}
+
+impl IconCache {
+    /// The texture for `name`, loading it on first use; None if it can't be loaded
+    pub fn texture(&mut self, ctx: &egui::Context, name: &str) -> Option<TextureHandle> {
+        self.textures
+            .entry(name.to_string())
+            .or_insert_with(|| match load_icon(name) {
+                Ok(image) => {
+                    Some(ctx.load_texture(format!("icon:{}", name), image, TextureOptions::LINEAR))
+                }
+                Err(e) => {
+                    log::warn!("Event icon unavailable: {:#}", e);
+                    None
+                }
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_icon_is_an_error() {
+        assert!(load_icon("data/icons/does_not_exist.png").is_err());
+    }
+}
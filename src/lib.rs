@@ -0,0 +1,263 @@
+//! The Lotus game: rules, content and the egui front end. `engine` drives the
+//! rules without any UI; the binary in `main.rs` runs the full game.
+
+mod app;
+pub mod audit;
+pub mod config;
+mod content_browser;
+pub mod converter;
+pub mod debug_socket;
+pub mod engine;
+mod fixture;
+pub mod game_data;
+pub mod game_rng;
+pub mod icons;
+pub mod logging;
+pub mod lotus_widget;
+pub mod number_format;
+pub mod paths;
+pub mod procedural;
+pub mod requirements;
+pub mod save;
+pub mod scenario;
+pub mod schema;
+pub mod stats;
+
+use crate::config::{CarryOver, GameConfig};
+use crate::content_browser::{BrowserFilter, BrowserSample, BrowserSimulation};
+use crate::game_data::{EventData, EventSource, GenerationError, GenerationMode, GenerationTrace};
+use crate::game_rng::GameRng;
+use crate::icons::IconCache;
+use crate::logging::LogHandle;
+use crate::lotus_widget::{BoardTheme, BoardView};
+use crate::procedural::library::ChoiceType;
+use crate::procedural::memory::ChoiceMemory;
+use crate::procedural::{EventDomain, PlayerIdentity};
+use crate::scenario::ScenarioPreset;
+use crate::stats::{GraphStat, RunStats, StatSnapshot};
+use eframe::egui;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+
+// --- Tiers ---
+/// Player tiers, D (0) to A+ (`TIER_COUNT - 1`). The board's rings, the SCS
+/// bands and event placement all cover exactly this range.
+pub const TIER_COUNT: usize = 5;
+
+// --- Floating Text Animation ---
+pub struct FloatingText {
+    pub text: String,
+    pub pos: egui::Pos2,
+    pub color: egui::Color32,
+    pub age: f32, // In seconds
+}
+
+// --- Tier Change Banner ---
+pub struct TierBanner {
+    pub old_tier: usize,
+    pub new_tier: usize,
+    pub started_at: f64, // ctx time in seconds
+}
+
+// --- Front End Screens ---
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    MainMenu,
+    NewGame, // Name, pronouns, and scenario entry
+    Settings,
+    Playing,
+    Summary, // Run over; offers New Game+
+}
+
+// Define the main application state
+pub struct LotusApp {
+    // Tunable game rules
+    config: GameConfig,
+
+    // The in-memory database of all possible events
+    event_database: Vec<EventData>,
+    // Pre-computed index for fast event lookups
+    event_index: HashMap<(usize, usize), (Vec<usize>, Vec<usize>)>,
+
+    // Player State
+    player_identity: PlayerIdentity,
+    player_tier: usize,
+    player_petal: usize,
+    social_credit_score: i32,
+    finances: i32,
+    legacy: u64, // Finances banked above `finance_cap`
+    career_level: u32,
+    guanxi_family: u32,
+    guanxi_network: u32,
+    guanxi_party: u32,
+    favors: u32, // Spent to skip a risk roll
+    bankruptcy_pending: bool,
+    used_grace: bool, // The one-time Tier D warning has been shown this run
+    scs_since_review: i32, // Rolling SCS change since the last review petal
+    player_age: u32,   // NEW: Player's age
+    life_stage: usize, // NEW: Current life stage (1 to the configured count)
+
+    // Game time: one turn per resolved event
+    turn: u64,
+
+    // Starting scenarios offered on the new game screen
+    scenarios: Vec<ScenarioPreset>,
+    selected_scenario: usize,
+    carry_over: CarryOver, // What the next New Game+ keeps
+
+    // Game Board config
+    num_petals_per_tier: usize,
+    board_theme: BoardTheme,
+    board_view: BoardView, // Player's zoom and pan, saved with the game
+    icons: IconCache,      // Event header textures, loaded on first use
+
+    // UI State
+    show_status_bar: bool,
+    show_event_source: bool, // Marks procedural event modals for testers
+    still_board: bool, // Freezes board animations for reproducible screenshots
+    debug_tools: bool, // Enables developer windows like the content browser
+    include_drafts: bool, // Debug builds only; see `drafts_enabled`
+    generation_mode: GenerationMode, // Restricts events to one pipeline for testing
+    content_browser_open: bool,
+    generation_trace: Option<GenerationTrace>, // How the last generated event was chosen
+    generation_trace_open: bool,
+    log_handle: Option<LogHandle>, // Set when the game logger is installed
+    log_viewer_open: bool,
+    stats_dashboard: bool, // Opt-in; adds the Stats button
+    stats_open: bool,
+    stat_graph_open: bool,
+    graph_stat: GraphStat,
+    browser_sample: Option<BrowserSample>,
+    browser_filter: BrowserFilter,
+    browser_matches: Option<(BrowserFilter, Vec<usize>)>, // Cached for the filter it was built from
+    browser_simulation: BrowserSimulation,
+    last_event_source: Option<EventSource>,
+    game_state: GameState,
+    saved_turn: Option<u64>, // Turn of the last save or load
+    quit_prompt_open: bool,
+    quit_confirmed: bool,
+    menu_message: Option<String>,
+    name_input: String,
+    current_event: Option<EventData>,
+    generation_error: Option<GenerationError>,
+    last_event_result: Option<String>,
+    floating_texts: VecDeque<FloatingText>,
+    tier_banner: Option<TierBanner>,
+    history: Vec<String>,
+
+    // Procedural event system
+    situation_library: procedural::SituationLibrary,
+
+    // Context tracking
+    recent_event_domains: VecDeque<EventDomain>,
+    encounter_map: HashMap<String, u64>, // situation id -> turn last seen
+    handcrafted_seen: HashMap<String, u64>, // handcrafted event title -> turn last shown
+    last_high_severity_turn: Option<u64>, // Paces High severity situations
+    choice_memory: VecDeque<ChoiceMemory>, // Oldest first
+    recent_results: VecDeque<String>,      // Result lines recently shown, oldest first
+    choice_cooldowns: HashMap<String, HashMap<ChoiceType, u64>>, // situation id -> archetype -> turn chosen
+    run_stats: RunStats,
+    stat_history: VecDeque<StatSnapshot>, // One per turn, oldest first
+    // Behind a RefCell so generation can borrow the app and the RNG together
+    rng: RefCell<GameRng>,
+}
+
+impl LotusApp {
+    /// Creates a new game with the default starting state
+    pub fn new(
+        config: GameConfig,
+        event_database: Vec<EventData>,
+        situation_library: procedural::SituationLibrary,
+        scenarios: Vec<ScenarioPreset>,
+        board_theme: BoardTheme,
+    ) -> Self {
+        let event_index = game_data::build_event_index(&event_database);
+        let favors = config.starting_favors;
+        let carry_over = config.new_game_plus;
+        let rng = GameRng::from_config(config.rng_seed);
+        Self {
+            config,
+            event_database,
+            event_index,
+            player_identity: PlayerIdentity::default(),
+            player_tier: 2,
+            player_petal: scenario::DEFAULT_START_PETAL,
+            num_petals_per_tier: 13,
+            board_theme,
+            board_view: BoardView::default(),
+            icons: IconCache::default(),
+            social_credit_score: 550, // Start in Tier B
+            finances: 1000,
+            legacy: 0,
+            career_level: 1,
+            guanxi_family: 1,
+            guanxi_network: 1,
+            guanxi_party: 0,
+            favors,
+            bankruptcy_pending: false,
+            used_grace: false,
+            turn: 0,
+            scenarios,
+            selected_scenario: 0,
+            carry_over,
+            scs_since_review: 0,
+            show_status_bar: false,
+            show_event_source: false,
+            still_board: false,
+            debug_tools: false,
+            include_drafts: false,
+            generation_mode: GenerationMode::default(),
+            content_browser_open: false,
+            generation_trace: None,
+            generation_trace_open: false,
+            log_handle: None,
+            log_viewer_open: false,
+            stats_dashboard: false,
+            stats_open: false,
+            stat_graph_open: false,
+            graph_stat: GraphStat::default(),
+            browser_sample: None,
+            browser_filter: BrowserFilter::default(),
+            browser_matches: None,
+            browser_simulation: BrowserSimulation::default(),
+            last_event_source: None,
+            game_state: GameState::MainMenu,
+            saved_turn: None,
+            quit_prompt_open: false,
+            quit_confirmed: false,
+            menu_message: None,
+            name_input: String::new(),
+            current_event: None,
+            generation_error: None,
+            last_event_result: None,
+            player_age: 18, // NEW: Initialize age
+            life_stage: 1,  // NEW: Initialize life stage
+            floating_texts: VecDeque::new(),
+            tier_banner: None,
+            history: Vec::new(),
+            situation_library,
+            recent_event_domains: VecDeque::new(),
+            encounter_map: HashMap::new(),
+            handcrafted_seen: HashMap::new(),
+            last_high_severity_turn: None,
+            choice_memory: VecDeque::new(),
+            recent_results: VecDeque::new(),
+            choice_cooldowns: HashMap::new(),
+            run_stats: RunStats::default(),
+            stat_history: VecDeque::new(),
+            rng: RefCell::new(rng),
+        }
+    }
+
+    /// Enables the developer Log window, which adjusts the installed logger
+    pub fn with_log_handle(mut self, handle: LogHandle) -> Self {
+        self.log_handle = Some(handle);
+        self
+    }
+
+    /// Limits event generation to the procedural or handcrafted pipeline
+    pub fn with_generation_mode(mut self, mode: GenerationMode) -> Self {
+        self.generation_mode = mode;
+        self
+    }
+}
@@ -0,0 +1,212 @@
+//! Process logger whose level can be changed from the debug tools while the
+//! game runs, keeping recent records for the in-app log viewer. `RUST_LOG`
+//! still sets the starting filter; picking a level in the viewer replaces it
+//! for every module.
+
+use super::LotusApp;
+use anyhow::{Result, anyhow};
+use eframe::egui::{self, Color32, RichText, ScrollArea, Window};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Records kept for the log viewer until `set_capacity` is called
+const DEFAULT_CAPACITY: usize = 500;
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+/// One captured log record
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// Bounded buffer of the latest records; older ones are dropped
+struct RecentRecords {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+}
+
+impl RecentRecords {
+    fn push(&mut self, line: LogLine) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.lines.len() >= self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+}
+
+struct GameLogger {
+    /// Filter from `RUST_LOG`, applied until a level is picked at runtime
+    env_filter: env_logger::Logger,
+    /// Writes to stderr; lets everything through (ignoring `RUST_LOG`'s
+    /// per-module directives) so it can follow the runtime level
+    output: env_logger::Logger,
+    overridden: AtomicBool,
+    recent: Mutex<RecentRecords>,
+}
+
+impl Log for GameLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && (self.overridden.load(Ordering::Relaxed) || self.env_filter.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.output.log(record);
+        if let Ok(mut recent) = self.recent.lock() {
+            recent.push(LogLine {
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {
+        self.output.flush();
+    }
+}
+
+/// Handle for adjusting the installed logger and reading its recent records
+#[derive(Clone, Copy)]
+pub struct LogHandle {
+    logger: &'static GameLogger,
+}
+
+impl LogHandle {
+    pub fn level(&self) -> LevelFilter {
+        log::max_level()
+    }
+
+    /// Applies `level` to every module from now on, replacing `RUST_LOG`
+    pub fn set_level(&self, level: LevelFilter) {
+        self.logger.overridden.store(true, Ordering::Relaxed);
+        log::set_max_level(level);
+        log::info!("Log level set to {}", level);
+    }
+
+    /// Recent records, oldest first
+    pub fn recent(&self) -> Vec<LogLine> {
+        self.logger
+            .recent
+            .lock()
+            .map(|recent| recent.lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut recent) = self.logger.recent.lock() {
+            recent.lines.clear();
+        }
+    }
+
+    /// Keeps at most `capacity` records, dropping the oldest beyond it
+    pub fn set_capacity(&self, capacity: usize) {
+        if let Ok(mut recent) = self.logger.recent.lock() {
+            recent.capacity = capacity;
+            let excess = recent.lines.len().saturating_sub(capacity);
+            recent.lines.drain(..excess);
+        }
+    }
+}
+
+/// Installs the game logger in place of `env_logger::init`. Fails if a
+/// logger is already installed.
+pub fn init() -> Result<LogHandle> {
+    let env_filter = env_logger::Builder::from_default_env().build();
+    let mut output = env_logger::Builder::new();
+    output.filter_level(LevelFilter::Trace);
+    if let Ok(style) = std::env::var("RUST_LOG_STYLE") {
+        output.parse_write_style(&style);
+    }
+    let output = output.build();
+    let start_level = env_filter.filter();
+    let logger: &'static GameLogger = Box::leak(Box::new(GameLogger {
+        env_filter,
+        output,
+        overridden: AtomicBool::new(false),
+        recent: Mutex::new(RecentRecords {
+            lines: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+        }),
+    }));
+    log::set_logger(logger).map_err(|e| anyhow!("Failed to install logger: {}", e))?;
+    log::set_max_level(start_level);
+    Ok(LogHandle { logger })
+}
+
+fn level_color(level: Level) -> Color32 {
+    match level {
+        Level::Error => Color32::from_rgb(230, 80, 80),
+        Level::Warn => Color32::from_rgb(218, 165, 32),
+        Level::Info => Color32::LIGHT_GRAY,
+        Level::Debug | Level::Trace => Color32::GRAY,
+    }
+}
+
+impl LotusApp {
+    pub(crate) fn show_log_viewer(&mut self, ctx: &egui::Context) {
+        let Some(handle) = self.log_handle else {
+            return;
+        };
+        let mut open = self.log_viewer_open;
+        Window::new("Log")
+            .open(&mut open)
+            .default_width(560.0)
+            .default_height(360.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut level = handle.level();
+                    egui::ComboBox::from_label("Level")
+                        .selected_text(level.as_str())
+                        .show_ui(ui, |ui| {
+                            for option in LEVELS {
+                                ui.selectable_value(&mut level, option, option.as_str());
+                            }
+                        });
+                    if level != handle.level() {
+                        handle.set_level(level);
+                    }
+                    if ui.button("Clear").clicked() {
+                        handle.clear();
+                    }
+                });
+                ui.separator();
+                // Copied out first: logging while the buffer is locked would deadlock
+                let lines = handle.recent();
+                ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                    if lines.is_empty() {
+                        ui.label(RichText::new("No records at this level yet.").weak());
+                    }
+                    for line in &lines {
+                        ui.label(
+                            RichText::new(format!(
+                                "{:<5} {}: {}",
+                                line.level, line.target, line.message
+                            ))
+                            .monospace()
+                            .color(level_color(line.level)),
+                        );
+                    }
+                });
+            });
+        self.log_viewer_open = open;
+    }
+}
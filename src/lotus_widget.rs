@@ -2,14 +2,82 @@ use eframe::egui::{
     self, Align2, Color32, FontId, Mesh, Pos2, Response, Rgba, Sense, Shape, Stroke, Ui, Vec2,
     Widget, vec2,
 };
+use anyhow::{Context, Result};
+use crate::procedural::EventDomain;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::f32::consts::TAU;
 
+// --- Board Theme ---
+/// Color and glyph for one tier's petals
+#[derive(Debug, Clone, Deserialize)]
+pub struct TierStyle {
+    pub color: [u8; 3],
+    pub glyph: String,
+}
+
+/// Tier colors and petal glyphs, embedded from `data/board_theme.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BoardTheme {
+    pub start_glyph: String,
+    pub review_glyph: String,
+    pub unknown_glyph: String,
+    /// Indexed by tier, innermost (D) first
+    pub tiers: Vec<TierStyle>,
+    /// Event window accents; domains without an entry use the plain theme
+    #[serde(default)]
+    pub domain_colors: HashMap<EventDomain, [u8; 3]>,
+}
+
+impl BoardTheme {
+    pub fn from_embedded() -> Result<Self> {
+        let theme_toml = include_str!("../data/board_theme.toml");
+        toml::from_str(theme_toml).context("Failed to parse board_theme.toml")
+    }
+
+    /// Base petal color for a tier
+    pub fn tier_color(&self, tier: usize) -> Color32 {
+        self.tiers
+            .get(tier)
+            .map_or(Color32::GRAY, |style| {
+                let [r, g, b] = style.color;
+                Color32::from_rgb(r, g, b)
+            })
+    }
+
+    /// Accent color for events from a domain
+    pub fn domain_accent(&self, domain: EventDomain) -> Option<Color32> {
+        self.domain_colors
+            .get(&domain)
+            .map(|&[r, g, b]| Color32::from_rgb(r, g, b))
+    }
+
+    /// Glyph drawn on a specific petal
+    pub fn petal_glyph(&self, tier: usize, petal: usize) -> &str {
+        if petal == 0 {
+            return &self.start_glyph;
+        }
+        if petal == 4 || petal == 8 {
+            return &self.review_glyph;
+        }
+        self.tiers
+            .get(tier)
+            .map_or(&self.unknown_glyph, |style| &style.glyph)
+    }
+}
+
 // --- Cached Geometry ---
+
+/// Number of segments used to sample each petal's bezier outline
+const OUTLINE_SEGMENTS: usize = 20;
+
 #[derive(Clone)]
 struct PetalInfo {
     base_shape: egui::epaint::CubicBezierShape,
+    // Sampled once at cache time; reused for the fill mesh and hit-testing
+    outline: Vec<Pos2>,
+    bounds: egui::Rect,
     text_pos: Pos2,
-    text: String,
     tier: usize,
     petal: usize,
     total_index: usize,
@@ -21,120 +89,328 @@ struct CachedGeometry {
     rect: egui::Rect,
 }
 
+/// Rect corners moving less than this (in points) reuse the cached geometry,
+/// so sub-pixel jitter while dragging a window edge doesn't rebuild every petal.
+const GEOMETRY_REBUILD_TOLERANCE: f32 = 1.5;
+
+impl CachedGeometry {
+    /// True if the widget rect moved or resized enough to need fresh geometry
+    fn is_stale_for(&self, rect: egui::Rect) -> bool {
+        (self.rect.min - rect.min).length() > GEOMETRY_REBUILD_TOLERANCE
+            || (self.rect.max - rect.max).length() > GEOMETRY_REBUILD_TOLERANCE
+    }
+}
+
+// --- Animation Style ---
+/// Timing and shape of the board's animations. All zero durations make every
+/// animation finish at once, for deterministic frames.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WidgetStyle {
+    /// Seconds a petal's hover bounce and color flush last
+    pub hover_duration: f64,
+    /// Extra scale at the top of the bounce (0.3 grows a petal by 30%)
+    pub bounce_overshoot: f32,
+    /// Size of the wobble as the bounce settles
+    pub bounce_wobble: f32,
+    /// Seconds the token takes to slide to a new petal
+    pub token_slide: f32,
+}
+
+impl Default for WidgetStyle {
+    fn default() -> Self {
+        Self {
+            hover_duration: 0.6,
+            bounce_overshoot: 0.3,
+            bounce_wobble: 0.05,
+            token_slide: 0.3,
+        }
+    }
+}
+
+impl WidgetStyle {
+    /// No hover animation and an instant token move
+    pub fn instant() -> Self {
+        Self {
+            hover_duration: 0.0,
+            token_slide: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// Hover progress (0-1) `elapsed` seconds after the hover began, or
+    /// None once the animation is over
+    pub fn hover_progress(&self, elapsed: f64) -> Option<f32> {
+        (elapsed >= 0.0 && elapsed < self.hover_duration)
+            .then(|| (elapsed / self.hover_duration) as f32)
+    }
+
+    /// Petal scale at hover progress `t`: grows past full size in the first
+    /// half, then settles back with a damped wobble
+    pub fn bounce_scale(&self, t: f32) -> f32 {
+        let peak = 1.0 + self.bounce_overshoot;
+        if t < 0.5 {
+            1.0 + (t * 2.0).powi(2) * self.bounce_overshoot
+        } else if t < 1.0 {
+            let t2 = (t - 0.5) * 2.0;
+            peak - t2 * self.bounce_overshoot
+                - (t2 * std::f32::consts::PI * 2.0).sin() * self.bounce_wobble * (1.0 - t2)
+        } else {
+            1.0
+        }
+    }
+}
+
+// --- Board View ---
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 3.0;
+/// Zoom factor change per point of scroll
+const ZOOM_PER_SCROLL_POINT: f32 = 0.002;
+
+/// Zoom and pan of the board, owned by the app so it is saved with the game.
+/// The default shows the whole board fitted to the widget.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BoardView {
+    pub zoom: f32,
+    /// Pan of the board center from the widget center, in points
+    pub offset: [f32; 2],
+}
+
+impl Default for BoardView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            offset: [0.0, 0.0],
+        }
+    }
+}
+
+impl BoardView {
+    /// Where the board is laid out when the widget fills `rect`
+    pub fn apply(&self, rect: egui::Rect) -> egui::Rect {
+        let [x, y] = self.offset;
+        egui::Rect::from_center_size(rect.center() + vec2(x, y), rect.size() * self.zoom)
+    }
+
+    /// Scrolls the zoom by `scroll` points, within its limits
+    pub fn zoom_by(&mut self, scroll: f32) {
+        self.zoom = (self.zoom * (scroll * ZOOM_PER_SCROLL_POINT).exp()).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+// --- Still Rendering ---
+/// Token glow strength when animations are frozen, halfway through its pulse
+const STILL_GLOW_PULSE: f32 = 0.5;
+
 /// Our custom widget.
-/// This widget is "dumb" - it just receives a total_index and renders it.
-pub struct LotusWidget {
+/// This widget is "dumb" - it just receives a total_index and renders it
+/// with the colors and glyphs of the theme it is given.
+pub struct LotusWidget<'a> {
     num_tiers: usize,
     num_petals_per_tier: usize,
     player_total_index: usize,
+    theme: &'a BoardTheme,
+    style: WidgetStyle,
+    still: bool, // No hover, token or glow animation
+    view: Option<&'a mut BoardView>, // Zoom and pan; the board stays fitted without one
 }
 
-impl LotusWidget {
-    pub fn new(num_tiers: usize, num_petals_per_tier: usize, player_total_index: usize) -> Self {
+impl<'a> LotusWidget<'a> {
+    pub fn new(
+        num_tiers: usize,
+        num_petals_per_tier: usize,
+        player_total_index: usize,
+        theme: &'a BoardTheme,
+        style: WidgetStyle,
+    ) -> Self {
         Self {
             num_tiers,
             num_petals_per_tier,
             player_total_index,
+            theme,
+            style,
+            still: false,
+            view: None,
         }
     }
 
-    /// Helper function to get the text for a specific petal
-    fn get_petal_text(tier: usize, petal: usize) -> String {
-        if petal == 0 {
-            return "🎉".to_string();
+    /// Renders without animation: petals at full size in their base colors
+    /// and the token resting on its petal, so a frame depends only on the
+    /// board state and not on the clock or pointer.
+    pub fn still(mut self, still: bool) -> Self {
+        self.still = still;
+        self
+    }
+
+    /// Lets the player zoom with the scroll wheel and pan by dragging,
+    /// keeping the result in `view`
+    pub fn view(mut self, view: &'a mut BoardView) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    /// Builds the petal geometry for the board laid out in `rect`
+    fn build_geometry(&self, rect: egui::Rect) -> CachedGeometry {
+        let center = rect.center();
+        let base_radius = rect.width().min(rect.height()) * 0.45;
+        let mut petals = Vec::new();
+        for tier in (0..self.num_tiers).rev() {
+            for petal in 0..self.num_petals_per_tier {
+                let total_index = tier * self.num_petals_per_tier + petal;
+                let tier_radius_factor = (tier as f32 + 1.0) / self.num_tiers as f32;
+                let tier_radius = base_radius * tier_radius_factor;
+                let tier_rotation =
+                    (tier as f32 * (TAU / self.num_petals_per_tier as f32)) / 2.0;
+                let angle =
+                    (petal as f32 / self.num_petals_per_tier as f32) * TAU + tier_rotation;
+
+                let p0 = center;
+                let p3 = center;
+                let petal_width = tier_radius * 0.9;
+                let petal_length = tier_radius * 1.1;
+                let cp1_base = vec2(-petal_width, -petal_length);
+                let cp2_base = vec2(petal_width, -petal_length);
+                let p1 = center + rotate_vec(cp1_base, angle);
+                let p2 = center + rotate_vec(cp2_base, angle);
+
+                let base_shape = egui::epaint::CubicBezierShape {
+                    points: [p0, p1, p2, p3],
+                    closed: true,
+                    fill: Color32::TRANSPARENT,
+                    stroke: Stroke::NONE.into(),
+                };
+
+                let outline: Vec<Pos2> = (0..=OUTLINE_SEGMENTS)
+                    .map(|i| base_shape.sample(i as f32 / OUTLINE_SEGMENTS as f32))
+                    .collect();
+                let bounds = egui::Rect::from_points(&outline);
+
+                let offset_vec = vec2(angle.sin(), -angle.cos()) * tier_radius * 0.75;
+                let text_pos = center + offset_vec;
+
+                petals.push(PetalInfo {
+                    base_shape,
+                    outline,
+                    bounds,
+                    text_pos,
+                    tier,
+                    petal,
+                    total_index,
+                });
+            }
         }
-        if petal == 4 || petal == 8 {
-            return "⚖️".to_string();
+        CachedGeometry { petals, rect }
+    }
+
+    /// Renders the board as a standalone SVG document, independent of egui.
+    /// Petals use their base tier colors with no hover animation, so the
+    /// output is identical for identical board states.
+    pub fn to_svg(&self, size: f32) -> String {
+        let rect = egui::Rect::from_min_size(Pos2::ZERO, vec2(size, size));
+        let geometry = self.build_geometry(rect);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n",
+            size
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"rgb(20,20,25)\"/>\n");
+
+        for petal in &geometry.petals {
+            let [p0, p1, p2, p3] = petal.base_shape.points;
+            let color = self.theme.tier_color(petal.tier);
+            svg.push_str(&format!(
+                "<path d=\"M {:.2} {:.2} C {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} Z\" fill=\"{}\" stroke=\"rgba(0,0,0,0.24)\" stroke-width=\"1\"/>\n",
+                p0.x, p0.y, p1.x, p1.y, p2.x, p2.y, p3.x, p3.y,
+                svg_color(color)
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"16\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                petal.text_pos.x,
+                petal.text_pos.y,
+                escape_xml(self.theme.petal_glyph(petal.tier, petal.petal))
+            ));
         }
-        match tier {
-            0 => "💀".to_string(),
-            1 => "⚠️".to_string(),
-            2 => "💼".to_string(),
-            3 => "🍲".to_string(),
-            4 => "🏆".to_string(),
-            _ => "??".to_string(),
+
+        // --- Player Token ---
+        if let Some(player_petal) = geometry
+            .petals
+            .iter()
+            .find(|p| p.total_index == self.player_total_index)
+        {
+            let token_radius = (size * 0.45 * 0.05).max(6.0);
+            svg.push_str(&format!(
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"rgb(255,220,0)\" stroke=\"rgba(0,0,0,0.59)\" stroke-width=\"{:.2}\"/>\n",
+                player_petal.text_pos.x,
+                player_petal.text_pos.y,
+                token_radius,
+                (token_radius * 0.2).max(1.5)
+            ));
         }
+
+        svg.push_str("</svg>\n");
+        svg
     }
 }
 
 /// Implementation of the `Widget` trait for our `LotusWidget`.
-impl Widget for LotusWidget {
-    fn ui(self, ui: &mut Ui) -> Response {
+impl Widget for LotusWidget<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
         let widget_id = ui.id().with("lotus_widget");
-        let mut response = ui.allocate_rect(ui.available_rect_before_wrap(), Sense::hover());
+        let sense = if self.view.is_some() && !self.still {
+            Sense::click_and_drag()
+        } else {
+            Sense::hover()
+        };
+        let response = ui.allocate_rect(ui.available_rect_before_wrap(), sense);
         let rect = response.rect;
-        let center = rect.center();
-        let base_radius = rect.width().min(rect.height()) * 0.45;
+
+        // --- Zoom & Pan ---
+        if !self.still
+            && let Some(view) = self.view.as_deref_mut()
+        {
+            if response.hovered() {
+                let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                if scroll != 0.0 {
+                    view.zoom_by(scroll);
+                }
+            }
+            if response.dragged() {
+                let delta = response.drag_delta();
+                view.offset[0] += delta.x;
+                view.offset[1] += delta.y;
+            }
+        }
+        let board_rect = self.view.as_deref().copied().unwrap_or_default().apply(rect);
+        let base_radius = board_rect.width().min(board_rect.height()) * 0.45;
 
         // --- Geometry Caching ---
         let mut cached_geo =
             ui.memory_mut(|mem| mem.data.get_persisted::<CachedGeometry>(widget_id).clone());
 
-        if cached_geo.as_ref().map_or(true, |c| c.rect != rect) {
-            let mut petals = Vec::new();
-            for tier in (0..self.num_tiers).rev() {
-                for petal in 0..self.num_petals_per_tier {
-                    let total_index = tier * self.num_petals_per_tier + petal;
-                    let tier_radius_factor = (tier as f32 + 1.0) / self.num_tiers as f32;
-                    let tier_radius = base_radius * tier_radius_factor;
-                    let tier_rotation =
-                        (tier as f32 * (TAU / self.num_petals_per_tier as f32)) / 2.0;
-                    let angle =
-                        (petal as f32 / self.num_petals_per_tier as f32) * TAU + tier_rotation;
-
-                    let p0 = center;
-                    let p3 = center;
-                    let petal_width = tier_radius * 0.9;
-                    let petal_length = tier_radius * 1.1;
-                    let cp1_base = vec2(-petal_width, -petal_length);
-                    let cp2_base = vec2(petal_width, -petal_length);
-                    let p1 = center + rotate_vec(cp1_base, angle);
-                    let p2 = center + rotate_vec(cp2_base, angle);
-
-                    let base_shape = egui::epaint::CubicBezierShape {
-                        points: [p0, p1, p2, p3],
-                        closed: true,
-                        fill: Color32::TRANSPARENT,
-                        stroke: Stroke::NONE.into(),
-                    };
-
-                    let offset_vec = vec2(angle.sin(), -angle.cos()) * tier_radius * 0.75;
-                    let text_pos = center + offset_vec;
-
-                    petals.push(PetalInfo {
-                        base_shape,
-                        text_pos,
-                        text: Self::get_petal_text(tier, petal),
-                        tier,
-                        petal,
-                        total_index,
-                    });
-                }
-            }
-            let new_cache = CachedGeometry { petals, rect };
+        if cached_geo.as_ref().is_none_or(|c| c.is_stale_for(board_rect)) {
+            let new_cache = self.build_geometry(board_rect);
             ui.memory_mut(|mem| mem.data.insert_persisted(widget_id, new_cache.clone()));
             cached_geo = Some(new_cache);
         }
 
         let cached_geo = cached_geo.unwrap();
-        let painter = ui.painter();
+        let painter = ui.painter_at(rect);
         let ctx = ui.ctx();
 
-        let tier_colors = [
-            Rgba::from(Color32::from_rgb(80, 80, 80)),
-            Rgba::from(Color32::from_rgb(255, 100, 100)),
-            Rgba::from(Color32::from_rgb(255, 180, 105)),
-            Rgba::from(Color32::from_rgb(105, 200, 255)),
-            Rgba::from(Color32::from_rgb(255, 220, 100)),
-        ];
         let text_font = FontId::proportional(16.0);
 
         // Separate petals into normal and animating for z-ordering
         let mut normal_petals = Vec::new();
         let mut animating_petal = None;
 
-        // Get pointer position - use interact_pos() which works even without interact() calls
-        let pointer_pos = ui.input(|i| i.pointer.interact_pos());
+        // Get pointer position - use interact_pos() which works even without interact() calls.
+        // A still board ignores the pointer, so no petal ever starts animating.
+        let pointer_pos = if self.still {
+            None
+        } else {
+            ui.input(|i| i.pointer.interact_pos())
+        };
 
         // Debug: Log pointer position once per frame
         if let Some(pos) = pointer_pos {
@@ -145,11 +421,12 @@ impl Widget for LotusWidget {
 
         for petal_info in &cached_geo.petals {
             let petal_id = response.id.with(petal_info.total_index);
-            let hover_rect = petal_info.base_shape.visual_bounding_rect();
+            let hover_rect = petal_info.bounds;
 
-            // Manual hover detection using pointer position
+            // Manual hover detection: cheap bounds check, then the cached outline
             let is_hovered = if let Some(pos) = pointer_pos {
-                let contains = hover_rect.contains(pos);
+                let contains =
+                    hover_rect.contains(pos) && polygon_contains(&petal_info.outline, pos);
                 if contains {
                     log::debug!("Pointer INSIDE petal {} hover_rect: {:?}", petal_info.total_index, hover_rect);
                 }
@@ -176,45 +453,33 @@ impl Widget for LotusWidget {
             }
             ui.memory_mut(|mem| mem.data.insert_temp(hover_state_id, is_hovered));
 
-            // Get animation progress (0.0 to 1.0 over animation duration)
-            let anim_duration = 0.6; // Total animation duration in seconds
+            // Get animation progress (0.0 to 1.0 over the style's hover duration)
             let anim_start_time = ui.memory(|mem| mem.data.get_temp::<f64>(petal_id.with("anim_start")));
             let current_time = ui.input(|i| i.time);
 
-            let (anim_progress, is_animating) = if let Some(start_time) = anim_start_time {
-                let elapsed = current_time - start_time;
-                if elapsed < anim_duration {
-                    ctx.request_repaint(); // Keep animating
-                    ((elapsed / anim_duration) as f32, true)
-                } else {
-                    (1.0, false)
+            let (anim_progress, is_animating) = if self.still {
+                (0.0, false)
+            } else if let Some(start_time) = anim_start_time {
+                match self.style.hover_progress(current_time - start_time) {
+                    Some(progress) => {
+                        ctx.request_repaint(); // Keep animating
+                        (progress, true)
+                    }
+                    None => (1.0, false),
                 }
             } else {
                 (0.0, false)
             };
 
-            // Bounce animation: ease out elastic
-            let scale = if is_animating && anim_progress < 1.0 {
-                // Bounce curve: overshoot then settle
-                let t = anim_progress;
-                let bounce = if t < 0.5 {
-                    // Scale up with overshoot
-                    1.0 + (t * 2.0).powi(2) * 0.3
-                } else {
-                    // Scale down with dampening bounce
-                    let t2 = (t - 0.5) * 2.0;
-                    1.3 - t2 * 0.3 - (t2 * std::f32::consts::PI * 2.0).sin() * 0.05 * (1.0 - t2)
-                };
-                bounce
+            // Bounce animation: overshoot then settle
+            let scale = if is_animating {
+                self.style.bounce_scale(anim_progress)
             } else {
                 1.0
             };
 
             // Iridescent color flush
-            let base_color_rgba = tier_colors
-                .get(petal_info.tier)
-                .cloned()
-                .unwrap_or(Rgba::from(Color32::GRAY));
+            let base_color_rgba = Rgba::from(self.theme.tier_color(petal_info.tier));
 
             let final_color = if is_animating && anim_progress < 1.0 {
                 // Create iridescent effect by cycling through rainbow colors
@@ -245,6 +510,7 @@ impl Widget for LotusWidget {
         for (petal_info, scale, final_color) in normal_petals {
             let (petal_mesh, petal_stroke_shape) = create_petal_mesh_from_base(
                 &petal_info.base_shape,
+                &petal_info.outline,
                 scale,
                 final_color,
                 Stroke::new(1.0, Color32::from_black_alpha(60)),
@@ -255,7 +521,7 @@ impl Widget for LotusWidget {
             painter.text(
                 petal_info.text_pos,
                 Align2::CENTER_CENTER,
-                &petal_info.text,
+                self.theme.petal_glyph(petal_info.tier, petal_info.petal),
                 text_font.clone(),
                 Color32::BLACK,
             );
@@ -265,6 +531,7 @@ impl Widget for LotusWidget {
         if let Some((petal_info, scale, final_color)) = animating_petal {
             let (petal_mesh, petal_stroke_shape) = create_petal_mesh_from_base(
                 &petal_info.base_shape,
+                &petal_info.outline,
                 scale,
                 final_color,
                 Stroke::new(2.0, Color32::from_black_alpha(100)),
@@ -275,7 +542,7 @@ impl Widget for LotusWidget {
             painter.text(
                 petal_info.text_pos,
                 Align2::CENTER_CENTER,
-                &petal_info.text,
+                self.theme.petal_glyph(petal_info.tier, petal_info.petal),
                 text_font.clone(),
                 Color32::BLACK,
             );
@@ -289,8 +556,11 @@ impl Widget for LotusWidget {
             .unwrap();
         let target_pos = player_petal_info.text_pos;
         let player_anim_id = response.id.with("player_token_pos");
-        let animated_x = ctx.animate_value_with_time(player_anim_id.with("x"), target_pos.x, 0.3);
-        let animated_y = ctx.animate_value_with_time(player_anim_id.with("y"), target_pos.y, 0.3);
+        let token_slide = if self.still { 0.0 } else { self.style.token_slide };
+        let animated_x =
+            ctx.animate_value_with_time(player_anim_id.with("x"), target_pos.x, token_slide);
+        let animated_y =
+            ctx.animate_value_with_time(player_anim_id.with("y"), target_pos.y, token_slide);
         let animated_pos = Pos2::new(animated_x, animated_y);
 
         let token_radius = (base_radius * 0.05).max(6.0);
@@ -298,8 +568,11 @@ impl Widget for LotusWidget {
 
         let time = ui.input(|i| i.time);
         let glow_anim_id = response.id.with("glow");
-        let pulse =
-            (ctx.animate_value_with_time(glow_anim_id, time as f32, 1.0) * 2.0).sin() * 0.5 + 0.5;
+        let pulse = if self.still {
+            STILL_GLOW_PULSE
+        } else {
+            (ctx.animate_value_with_time(glow_anim_id, time as f32, 1.0) * 2.0).sin() * 0.5 + 0.5
+        };
         let glow_radius = token_radius * (1.5 + pulse * 0.5);
         let glow_color = Color32::from_rgba_premultiplied(255, 220, 0, (pulse * 80.0) as u8);
 
@@ -317,14 +590,15 @@ impl Widget for LotusWidget {
 
 fn create_petal_mesh_from_base(
     base_shape: &egui::epaint::CubicBezierShape,
+    outline: &[Pos2],
     scale: f32,
     fill_color: Color32,
     stroke: Stroke,
 ) -> (Mesh, Shape) {
     let mut scaled_points = base_shape.points;
     let center = scaled_points[0];
-    for i in 1..4 {
-        scaled_points[i] = center + (scaled_points[i] - center) * scale;
+    for point in scaled_points.iter_mut().skip(1) {
+        *point = center + (*point - center) * scale;
     }
 
     let bezier = egui::epaint::CubicBezierShape {
@@ -343,23 +617,50 @@ fn create_petal_mesh_from_base(
         (fill_color.a() as f32 * 0.8) as u8,
     );
 
-    const N_POINTS: usize = 20;
     mesh.colored_vertex(center, center_color);
 
-    for i in 0..=N_POINTS {
-        let t = i as f32 / N_POINTS as f32;
-        let pos = bezier.sample(t);
-        mesh.colored_vertex(pos, edge_color);
+    // Both end points sit on the center, so scaling the cached outline about
+    // the center matches sampling the scaled bezier.
+    for &pos in outline {
+        mesh.colored_vertex(center + (pos - center) * scale, edge_color);
     }
 
-    for i in 1..=N_POINTS {
+    for i in 1..=OUTLINE_SEGMENTS {
         mesh.add_triangle(0, i as u32, (i + 1) as u32);
     }
-    mesh.add_triangle(0, N_POINTS as u32, 1);
+    mesh.add_triangle(0, OUTLINE_SEGMENTS as u32, 1);
 
     (mesh, Shape::CubicBezier(bezier))
 }
 
+/// Even-odd point-in-polygon test against a closed outline
+fn polygon_contains(outline: &[Pos2], point: Pos2) -> bool {
+    let mut inside = false;
+    let mut j = outline.len().wrapping_sub(1);
+    for (i, a) in outline.iter().enumerate() {
+        let b = outline[j];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Formats an opaque color as an SVG `rgb()` value
+fn svg_color(color: Color32) -> String {
+    format!("rgb({},{},{})", color.r(), color.g(), color.b())
+}
+
+/// Escapes the characters that are special inside SVG text nodes
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 /// Helper function to rotate a Vec2
 fn rotate_vec(v: Vec2, angle: f32) -> Vec2 {
     let (sin, cos) = angle.sin_cos();
@@ -390,3 +691,62 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Rgba {
 
     Rgba::from_rgb(r + m, g + m, b + m)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TIER_COUNT;
+    use crate::config::GameConfig;
+
+    #[test]
+    fn embedded_style_is_the_default() -> Result<()> {
+        assert_eq!(GameConfig::from_embedded()?.widget_style, WidgetStyle::default());
+        Ok(())
+    }
+
+    /// The bounce starts and ends at full size and peaks at the overshoot
+    #[test]
+    fn bounce_returns_to_full_size() {
+        let style = WidgetStyle::default();
+        let peak = 1.0 + style.bounce_overshoot;
+        for (t, expected) in [(0.0, 1.0), (0.5, peak), (1.0, 1.0)] {
+            let scale = style.bounce_scale(t);
+            assert!((scale - expected).abs() <= 1e-4, "{} at {}", scale, t);
+        }
+    }
+
+    #[test]
+    fn hover_spans_its_duration() {
+        let style = WidgetStyle::default();
+        assert_eq!(style.hover_progress(style.hover_duration / 2.0), Some(0.5));
+        assert_eq!(style.hover_progress(style.hover_duration), None);
+    }
+
+    #[test]
+    fn instant_style_never_animates() {
+        let instant = WidgetStyle::instant();
+        assert_eq!(instant.hover_progress(0.0), None);
+        assert_eq!(instant.token_slide, 0.0);
+    }
+
+    #[test]
+    fn default_view_fits_the_board() {
+        let rect = egui::Rect::from_min_size(egui::pos2(10.0, 20.0), egui::vec2(400.0, 300.0));
+        assert_eq!(BoardView::default().apply(rect), rect);
+    }
+
+    #[test]
+    fn zoom_stays_in_range() {
+        let mut view = BoardView::default();
+        view.zoom_by(1e6);
+        assert_eq!(view.zoom, MAX_ZOOM);
+        view.zoom_by(-1e6);
+        assert_eq!(view.zoom, MIN_ZOOM);
+    }
+
+    #[test]
+    fn theme_styles_every_tier() -> Result<()> {
+        assert_eq!(BoardTheme::from_embedded()?.tiers.len(), TIER_COUNT);
+        Ok(())
+    }
+}
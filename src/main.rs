@@ -1,75 +1,66 @@
-// Declare our new modules
-mod app;
-mod converter;
-mod game_data;
-mod lotus_widget;
-mod procedural;
-
-use crate::game_data::EventData;
-use crate::procedural::EventDomain;
 use eframe::egui;
-use std::collections::{HashMap, HashSet, VecDeque};
-
-// --- Floating Text Animation ---
-pub struct FloatingText {
-    pub text: String,
-    pub pos: egui::Pos2,
-    pub color: egui::Color32,
-    pub age: f32, // In seconds
-}
-
-// Define the main application state
-pub struct LotusApp {
-    // The in-memory database of all possible events
-    event_database: Vec<EventData>,
-    // Pre-computed index for fast event lookups
-    event_index: HashMap<(usize, usize), (Vec<usize>, Vec<usize>)>,
-
-    // Player State
-    player_tier: usize,
-    player_petal: usize,
-    social_credit_score: i32,
-    finances: i32,
-    career_level: u32,
-    guanxi_family: u32,
-    guanxi_network: u32,
-    guanxi_party: u32,
-    player_age: u32,   // NEW: Player's age
-    life_stage: usize, // NEW: Current life stage (1-4)
-
-    // Game Board config
-    num_petals_per_tier: usize,
-    num_tiers: usize,
-
-    // UI State
-    current_event: Option<EventData>,
-    last_event_result: Option<String>,
-    floating_texts: VecDeque<FloatingText>,
-    history: Vec<String>,
-
-    // Procedural event system
-    situation_library: procedural::SituationLibrary,
-
-    // Context tracking
-    recent_event_domains: VecDeque<EventDomain>,
-    encounter_history: HashSet<String>,
-    event_counter: usize,
-    encounter_map: HashMap<String, usize>,
-}
+use game_experiment::config::GameConfig;
+use game_experiment::game_data::EventData;
+use game_experiment::lotus_widget::BoardTheme;
+use game_experiment::paths::Paths;
+use game_experiment::{LotusApp, audit, converter, game_data, logging, procedural, scenario};
 
 fn main() -> anyhow::Result<()> {
-    // Initialize logger (set RUST_LOG=debug for detailed logging)
-    env_logger::init();
+    // Initialize logger (set RUST_LOG=debug for detailed logging; the level
+    // can also be changed in game from the developer tools' Log window)
+    let log_handle = logging::init()?;
 
     // 1. Check command line arguments
     let args: Vec<String> = std::env::args().collect();
+    let converting = args.contains(&"--convert".to_string());
+    // Content locations, from --events-json etc. or their LOTUS_* variables
+    let paths = Paths::resolve(&args, converting)?;
+    for line in paths.describe_overrides() {
+        log::info!("Content override: {}", line);
+    }
+
+    // Headless game driven by debug commands; normal builds never listen
+    #[cfg(feature = "debug-socket")]
+    if let Some(position) = args.iter().position(|arg| arg == "--debug-socket") {
+        let address = args
+            .get(position + 1)
+            .filter(|arg| !arg.starts_with("--"))
+            .map_or("127.0.0.1:7878", String::as_str);
+        let engine = game_experiment::engine::Engine::new(GameConfig::from_embedded()?, &paths)?;
+        return game_experiment::debug_socket::serve(address, engine);
+    }
 
-    if args.contains(&"--convert".to_string()) {
+    if converting {
         // 2. If --convert is found, run the converter logic
         println!("Running event data converter...");
-        converter::run_converter()?; // This will propogate any errors
-        println!("Successfully generated 'src/events.json' from CSVs. Exiting.");
+        converter::run_converter(&paths)?; // This will propogate any errors
+        println!("Successfully generated the events JSON from CSVs. Exiting.");
         Ok(()) // Exit successfully
+    } else if args.contains(&"--export".to_string()) {
+        // Regenerate the CSVs from the JSON, the reverse of --convert
+        println!("Exporting event data to CSV...");
+        converter::run_exporter(&paths)?;
+        println!(
+            "Successfully wrote '{}' and '{}'. Exiting.",
+            paths.events_csv.display(),
+            paths.options_csv.display()
+        );
+        Ok(())
+    } else if let Some(position) = args.iter().position(|arg| arg == "--diff-content") {
+        // Compare two procedural content directories, old side first
+        let (Some(old), Some(new)) = (args.get(position + 1), args.get(position + 2)) else {
+            anyhow::bail!("--diff-content needs two content directories");
+        };
+        let old_library = procedural::SituationLibrary::from_dir(old.as_ref())?;
+        let new_library = procedural::SituationLibrary::from_dir(new.as_ref())?;
+        let diff = procedural::content_diff::diff_libraries(&old_library, &new_library)?;
+        println!("Content changes from {} to {}:", old, new);
+        print!("{}", diff);
+        Ok(())
+    } else if args.contains(&"--audit".to_string()) {
+        // Run the content self-checks without starting the game
+        println!("Running content audit...");
+        audit::run_audit()
     } else {
         // 3. Otherwise, run the game
         println!("Starting game...");
@@ -81,25 +72,14 @@ fn main() -> anyhow::Result<()> {
         };
 
         // --- Asset Management ---
-        let event_json = include_str!("events.json").to_string();
-
-        let event_database: Vec<EventData> = serde_json::from_str(&event_json)
-            .expect("Failed to parse events.json. Check file format.");
-
-        // --- Pre-compute the event index ---
-        let mut event_index = HashMap::new();
-        for (i, event) in event_database.iter().enumerate() {
-            for tier in event.min_tier..=event.max_tier {
-                let (tier_specific, generic) = event_index
-                    .entry((event.life_stage, tier))
-                    .or_insert_with(|| (Vec::new(), Vec::new()));
-                if event.is_generic {
-                    generic.push(i);
-                } else {
-                    tier_specific.push(i);
-                }
-            }
-        }
+        let event_database = load_events(&paths);
+        let config = GameConfig::from_embedded().expect("Failed to load game config");
+        log_handle.set_capacity(config.log_buffer_lines);
+        let scenarios = scenario::load_scenarios().expect("Failed to load scenario presets");
+        let board_theme = BoardTheme::from_embedded().expect("Failed to load board theme");
+        // Either pipeline can be switched off to test the other on its own
+        let generation_mode = game_data::GenerationMode::from_args(&args)?;
+        log::info!("Event generation: {}", generation_mode.label());
 
         // eframe::run_native returns an eframe::Result, so we map the error
         // to anyhow::Error to match our main function's return type.
@@ -114,36 +94,72 @@ fn main() -> anyhow::Result<()> {
                 cc.egui_ctx.set_visuals(visuals);
 
                 // Load situation library
-                let situation_library = procedural::SituationLibrary::from_embedded_configs()
-                    .expect("Failed to load situation library");
-
-                Ok(Box::new(LotusApp {
-                    event_database,
-                    event_index,
-                    player_tier: 2,
-                    player_petal: 1,
-                    num_petals_per_tier: 13,
-                    num_tiers: 5,
-                    social_credit_score: 550, // Start in Tier B
-                    finances: 1000,
-                    career_level: 1,
-                    guanxi_family: 1,
-                    guanxi_network: 1,
-                    guanxi_party: 0,
-                    current_event: None,
-                    last_event_result: None,
-                    player_age: 18, // NEW: Initialize age
-                    life_stage: 1,  // NEW: Initialize life stage
-                    floating_texts: VecDeque::new(),
-                    history: Vec::new(),
-                    situation_library,
-                    recent_event_domains: VecDeque::new(),
-                    encounter_history: HashSet::new(),
-                    event_counter: 0,
-                    encounter_map: HashMap::new(),
-                }))
+                let situation_library = match &paths.procedural_dir {
+                    Some(dir) => procedural::SituationLibrary::from_dir(dir),
+                    None => procedural::SituationLibrary::from_embedded_configs(),
+                }
+                .expect("Failed to load situation library");
+                situation_library
+                    .check_life_stages(config.life_stage_count())
+                    .expect("Situation life stages don't fit the configured stages");
+                situation_library
+                    .check_tiers()
+                    .expect("Situation tiers don't fit the board");
+                if config.duplicate_situation_threshold <= 1.0 {
+                    situation_library.warn_duplicates(config.duplicate_situation_threshold);
+                }
+                situation_library.warn_unreachable_requirements(&config.requirement_ceiling);
+
+                Ok(Box::new(
+                    LotusApp::new(
+                        config,
+                        event_database,
+                        situation_library,
+                        scenarios,
+                        board_theme,
+                    )
+                    .with_log_handle(log_handle)
+                    .with_generation_mode(generation_mode),
+                ))
             }),
         )
         .map_err(|e| anyhow::anyhow!("eframe error: {}", e))
     }
 }
+
+/// The handcrafted events from events.json, or if those don't load, the events
+/// converted afresh from the CSVs. With neither, the game starts with no
+/// handcrafted events and relies on procedural generation.
+fn load_events(paths: &Paths) -> Vec<EventData> {
+    let (source, loaded) = match &paths.events_json {
+        Some(path) => (
+            path.display().to_string(),
+            game_data::load_event_database_from(path),
+        ),
+        None => (
+            game_data::events_source().to_string(),
+            game_data::load_event_database(),
+        ),
+    };
+    log::info!("Handcrafted events: {}", source);
+    let json_error = match loaded {
+        Ok(events) => return events,
+        Err(e) => e,
+    };
+    log::error!("Failed to load events.json ({}): {:#}", source, json_error);
+    match converter::events_from_csv(paths) {
+        Ok(events) => {
+            log::warn!(
+                "Loaded {} handcrafted events from the CSVs instead; run --convert to \
+                 regenerate events.json",
+                events.len()
+            );
+            events
+        }
+        Err(e) => {
+            log::error!("Failed to convert the event CSVs: {:#}", e);
+            log::warn!("Starting with no handcrafted events; every event will be procedural");
+            Vec::new()
+        }
+    }
+}
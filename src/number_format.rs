@@ -0,0 +1,71 @@
+//! Display formatting for large stat values: thousands separators, and
+//! compact units once a value reaches the millions. SCS stays a plain
+//! integer, as its band is small.
+
+/// Compact units, largest first, with the value each starts at
+const UNITS: [(f64, &str); 2] = [(1e9, "B"), (1e6, "M")];
+
+/// `value` with a comma between each group of three digits, e.g. "-12,500"
+pub fn thousands(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if value < 0 {
+        grouped.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// "1.0M" or "2.5B" from a million up, otherwise `thousands`
+pub fn amount(value: i64) -> String {
+    let magnitude = value.unsigned_abs() as f64;
+    // Starts a unit early where the smaller one would round up to "1000.0"
+    match UNITS.iter().find(|(size, _)| magnitude >= size - size / 20_000.0) {
+        Some((size, unit)) => format!("{:.1}{}", value as f64 / size, unit),
+        None => thousands(value),
+    }
+}
+
+/// `amount` with a leading "+" on gains, for deltas
+pub fn signed_amount(value: i64) -> String {
+    if value > 0 {
+        format!("+{}", amount(value))
+    } else {
+        amount(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_group_then_compact_without_rounding_up() {
+        for (value, expected) in [
+            (0, "0"),
+            (999, "999"),
+            (-1_500, "-1,500"),
+            (250_000, "250,000"),
+            (999_949, "999,949"),
+            (1_000_000, "1.0M"),
+            (-2_450_000, "-2.5M"),
+            (999_960_000, "1.0B"),
+            (12_300_000_000, "12.3B"),
+        ] {
+            assert_eq!(amount(value), expected, "{}", value);
+        }
+        assert_eq!(thousands(1_000_000), "1,000,000");
+    }
+
+    #[test]
+    fn deltas_keep_their_sign() {
+        assert_eq!(signed_amount(1_500), "+1,500");
+        assert_eq!(signed_amount(-1_500), "-1,500");
+        assert_eq!(signed_amount(0), "0");
+    }
+}
@@ -0,0 +1,183 @@
+//! Where content is read from (and `--convert`/`--export` write to). Each
+//! location can be overridden from the command line or the environment, to
+//! run against another content pack without recompiling; a flag wins over
+//! its variable.
+
+use crate::procedural::library::CONTENT_FILES;
+use anyhow::{Result, bail, ensure};
+use std::path::{Path, PathBuf};
+
+/// The flag and environment variable that override one location
+struct Override {
+    flag: &'static str,
+    env: &'static str,
+}
+
+const EVENTS_JSON: Override = Override {
+    flag: "--events-json",
+    env: "LOTUS_EVENTS_JSON",
+};
+const EVENTS_CSV: Override = Override {
+    flag: "--events-csv",
+    env: "LOTUS_EVENTS_CSV",
+};
+const OPTIONS_CSV: Override = Override {
+    flag: "--options-csv",
+    env: "LOTUS_OPTIONS_CSV",
+};
+const PROCEDURAL_DIR: Override = Override {
+    flag: "--procedural-dir",
+    env: "LOTUS_PROCEDURAL_DIR",
+};
+
+/// Content locations, resolved once at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    /// None loads the events.json this build carries (see `events_source`)
+    pub events_json: Option<PathBuf>,
+    pub events_csv: PathBuf,
+    pub options_csv: PathBuf,
+    /// None uses the embedded situation and variable TOML
+    pub procedural_dir: Option<PathBuf>,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            events_json: None,
+            events_csv: PathBuf::from("data/events.csv"),
+            options_csv: PathBuf::from("data/event_options.csv"),
+            procedural_dir: None,
+        }
+    }
+}
+
+impl Override {
+    /// The value after the flag, else the environment variable, if either is set
+    fn resolve(&self, args: &[String]) -> Result<Option<PathBuf>> {
+        if let Some(position) = args.iter().position(|arg| arg == self.flag) {
+            let Some(value) = args.get(position + 1).filter(|value| !value.starts_with("--"))
+            else {
+                bail!("{} needs a path", self.flag);
+            };
+            return Ok(Some(PathBuf::from(value)));
+        }
+        Ok(std::env::var_os(self.env).map(PathBuf::from))
+    }
+}
+
+impl Paths {
+    /// Applies any overrides in `args` and the environment. Every overridden
+    /// input must exist; `converting` lets the events JSON be a new file,
+    /// since `--convert` writes it.
+    pub fn resolve(args: &[String], converting: bool) -> Result<Self> {
+        let defaults = Self::default();
+        let paths = Self {
+            events_json: EVENTS_JSON.resolve(args)?,
+            events_csv: EVENTS_CSV.resolve(args)?.unwrap_or_else(|| defaults.events_csv.clone()),
+            options_csv: OPTIONS_CSV.resolve(args)?.unwrap_or_else(|| defaults.options_csv.clone()),
+            procedural_dir: PROCEDURAL_DIR.resolve(args)?,
+        };
+
+        if let Some(json) = &paths.events_json {
+            if converting {
+                let parent = json.parent().filter(|p| !p.as_os_str().is_empty());
+                ensure!(
+                    parent.is_none_or(Path::is_dir),
+                    "{}: the directory for {} does not exist",
+                    EVENTS_JSON.flag,
+                    json.display()
+                );
+            } else {
+                require_file(&EVENTS_JSON, json)?;
+            }
+        }
+        for (location, csv, default) in [
+            (&EVENTS_CSV, &paths.events_csv, &defaults.events_csv),
+            (&OPTIONS_CSV, &paths.options_csv, &defaults.options_csv),
+        ] {
+            if csv != default {
+                require_file(location, csv)?;
+            }
+        }
+        if let Some(dir) = &paths.procedural_dir {
+            ensure!(
+                dir.is_dir(),
+                "{}: {} is not a directory",
+                PROCEDURAL_DIR.flag,
+                dir.display()
+            );
+            for name in CONTENT_FILES {
+                require_file(&PROCEDURAL_DIR, &dir.join(name))?;
+            }
+        }
+        Ok(paths)
+    }
+
+    /// One line per overridden location, for the startup log
+    pub fn describe_overrides(&self) -> Vec<String> {
+        let defaults = Self::default();
+        let mut lines = Vec::new();
+        if let Some(json) = &self.events_json {
+            lines.push(format!("events JSON: {}", json.display()));
+        }
+        if self.events_csv != defaults.events_csv {
+            lines.push(format!("events CSV: {}", self.events_csv.display()));
+        }
+        if self.options_csv != defaults.options_csv {
+            lines.push(format!("options CSV: {}", self.options_csv.display()));
+        }
+        if let Some(dir) = &self.procedural_dir {
+            lines.push(format!("procedural content: {}", dir.display()));
+        }
+        lines
+    }
+}
+
+fn require_file(location: &Override, path: &Path) -> Result<()> {
+    ensure!(
+        path.is_file(),
+        "{} (or {}): {} does not exist",
+        location.flag,
+        location.env,
+        path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn flags_override_locations() -> Result<()> {
+        let list = args(&[
+            "lotus",
+            "--events-csv",
+            "data/events.csv",
+            "--procedural-dir",
+            "data/procedural",
+        ]);
+        let paths = Paths::resolve(&list, false)?;
+        assert_eq!(paths.procedural_dir.as_deref(), Some(Path::new("data/procedural")));
+        assert_eq!(paths.events_csv, Path::new("data/events.csv"));
+        Ok(())
+    }
+
+    #[test]
+    fn overrides_must_point_at_content() {
+        for (list, converting) in [
+            (&["lotus", "--events-json", "missing/events.json"][..], false),
+            (&["lotus", "--events-json", "missing/events.json"][..], true),
+            (&["lotus", "--options-csv", "data/missing.csv"][..], false),
+            (&["lotus", "--procedural-dir", "data"][..], false),
+            (&["lotus", "--events-csv"][..], false),
+        ] {
+            assert!(Paths::resolve(&args(list), converting).is_err(), "{:?}", &list[1..]);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! What changed between two procedural content directories, for reviewing
+//! merges of large TOML edits (`--diff-content <dir_a> <dir_b>`). Situations
+//! are matched by id; variable lists are compared entry by entry.
+
+use super::library::{SituationLibrary, SituationTemplate};
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A variable list whose entries differ between the two libraries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableChange {
+    /// Variable name; colleague descriptors are `colleague_descriptors.<tier>`
+    pub name: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Differences from library `a` to library `b`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentDiff {
+    /// Ids only in `b`, in its load order
+    pub added: Vec<String>,
+    /// Ids only in `a`, in its load order
+    pub removed: Vec<String>,
+    /// Ids in both whose templates differ, with the fields that changed
+    pub modified: Vec<(String, Vec<String>)>,
+    pub variables: Vec<VariableChange>,
+}
+
+impl ContentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.variables.is_empty()
+    }
+}
+
+/// Compares two libraries, `a` being the old side
+pub fn diff_libraries(a: &SituationLibrary, b: &SituationLibrary) -> Result<ContentDiff> {
+    let mut diff = ContentDiff::default();
+    for situation in a.situations() {
+        match b.situation_by_id(&situation.id) {
+            None => diff.removed.push(situation.id.clone()),
+            Some(other) => {
+                let fields = changed_fields(situation, other)?;
+                if !fields.is_empty() {
+                    diff.modified.push((situation.id.clone(), fields));
+                }
+            }
+        }
+    }
+    diff.added = b
+        .situations()
+        .iter()
+        .filter(|situation| a.situation_by_id(&situation.id).is_none())
+        .map(|situation| situation.id.clone())
+        .collect();
+
+    let old = variable_lists(&serde_json::to_value(&a.variables)?);
+    let new = variable_lists(&serde_json::to_value(&b.variables)?);
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        let before = old.get(name).map_or(&[][..], Vec::as_slice);
+        let after = new.get(name).map_or(&[][..], Vec::as_slice);
+        let change = VariableChange {
+            name: name.clone(),
+            added: after.iter().filter(|e| !before.contains(e)).cloned().collect(),
+            removed: before.iter().filter(|e| !after.contains(e)).cloned().collect(),
+        };
+        if !change.added.is_empty() || !change.removed.is_empty() {
+            diff.variables.push(change);
+        }
+    }
+    Ok(diff)
+}
+
+/// Top-level template fields whose values differ
+fn changed_fields(a: &SituationTemplate, b: &SituationTemplate) -> Result<Vec<String>> {
+    let (Value::Object(a), Value::Object(b)) = (serde_json::to_value(a)?, serde_json::to_value(b)?)
+    else {
+        return Ok(Vec::new());
+    };
+    let mut fields: Vec<String> = a
+        .iter()
+        .filter(|(field, value)| b.get(*field) != Some(*value))
+        .map(|(field, _)| field.clone())
+        .collect();
+    fields.extend(b.keys().filter(|field| !a.contains_key(*field)).cloned());
+    Ok(fields)
+}
+
+/// Every variable list by name, flattening maps of lists into `map.key`
+fn variable_lists(variables: &Value) -> BTreeMap<String, Vec<String>> {
+    let mut lists = BTreeMap::new();
+    let Value::Object(fields) = variables else {
+        return lists;
+    };
+    for (name, value) in fields {
+        match value {
+            Value::Object(map) => {
+                for (key, list) in map {
+                    lists.insert(format!("{}.{}", name, key), strings(list));
+                }
+            }
+            list => {
+                lists.insert(name.clone(), strings(list));
+            }
+        }
+    }
+    lists
+}
+
+fn strings(list: &Value) -> Vec<String> {
+    list.as_array()
+        .map(|entries| entries.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+impl fmt::Display for ContentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences.");
+        }
+        writeln!(
+            f,
+            "Situations: {} added, {} removed, {} modified",
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len()
+        )?;
+        for id in &self.added {
+            writeln!(f, "  + {}", id)?;
+        }
+        for id in &self.removed {
+            writeln!(f, "  - {}", id)?;
+        }
+        for (id, fields) in &self.modified {
+            writeln!(f, "  ~ {} ({})", id, fields.join(", "))?;
+        }
+        writeln!(f, "Variables: {} changed", self.variables.len())?;
+        for change in &self.variables {
+            writeln!(
+                f,
+                "  ~ {} (+{} -{})",
+                change.name,
+                change.added.len(),
+                change.removed.len()
+            )?;
+            for entry in &change.added {
+                writeln!(f, "      + {:?}", entry)?;
+            }
+            for entry in &change.removed {
+                writeln!(f, "      - {:?}", entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_library_matches_itself() -> Result<()> {
+        let library = SituationLibrary::from_embedded_configs()?;
+        assert!(diff_libraries(&library, &library)?.is_empty());
+        Ok(())
+    }
+
+    /// One situation removed, one added, one edited and a variable extended
+    #[test]
+    fn reports_each_kind_of_change() -> Result<()> {
+        let library = SituationLibrary::from_embedded_configs()?;
+        let mut situations = library.situations().to_vec();
+        let removed = situations.remove(0).id;
+        let mut added = situations[0].clone();
+        added.id = format!("{}_copy", added.id);
+        situations[0].base_risk = situations[0].base_risk.wrapping_add(1);
+        let modified = situations[0].id.clone();
+        situations.push(added.clone());
+        let mut variables = library.variables.clone();
+        variables.work_time.push("during the test".to_string());
+        let edited = SituationLibrary::new(situations, variables)?;
+
+        let diff = diff_libraries(&library, &edited)?;
+        assert_eq!(diff.added, [added.id]);
+        assert_eq!(diff.removed, [removed]);
+        assert_eq!(diff.modified, [(modified, vec!["base_risk".to_string()])]);
+        assert_eq!(
+            diff.variables,
+            [VariableChange {
+                name: "work_time".to_string(),
+                added: vec!["during the test".to_string()],
+                removed: Vec::new(),
+            }]
+        );
+        Ok(())
+    }
+}
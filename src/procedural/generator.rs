@@ -1,44 +1,125 @@
-use super::library::{ChoiceArchetype, EventDomain, SituationTemplate};
-use super::risk_calculator::{PlayerStats, calculate_risk};
+use super::library::{ChoiceArchetype, ChoiceType, EventDomain, Severity, SituationTemplate};
+use super::memory::recall;
+use super::risk_calculator::{PlayerStats, explain_risk};
 use super::stat_calculator::{calculate_failure_stats, calculate_stats};
-use super::text_assembly::{assemble_choice_text, assemble_description};
-use crate::LotusApp;
-use crate::game_data::{EventData, EventOption, EventOutcome};
+use super::text_assembly::{
+    SubstitutionContext, assemble_choice_text, assemble_description, resolve_placeholders,
+};
+use crate::{LotusApp, TIER_COUNT};
+use crate::game_data::{EventData, EventOption, EventOutcome, skip_option};
 use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+
+/// How many of the most recently resolved domains are excluded from selection
+const RECENT_DOMAIN_FILTER_SIZE: usize = 2;
+/// Situations may be placed this many tiers outside their own tier range
+pub const TIER_PLACEMENT_TOLERANCE: usize = 1;
+
+/// Tiers whose situations a player at `player_tier` may be placed in:
+/// `player_tier ± TIER_PLACEMENT_TOLERANCE`, kept within the board
+pub fn placement_range(player_tier: usize) -> RangeInclusive<usize> {
+    let tier = player_tier.min(TIER_COUNT - 1);
+    tier.saturating_sub(TIER_PLACEMENT_TOLERANCE)
+        ..=(tier + TIER_PLACEMENT_TOLERANCE).min(TIER_COUNT - 1)
+}
+
+/// How many situations each filter set aside, in the order they are applied
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterCounts {
+    pub total: usize,
+    pub draft: usize,
+    pub tier: usize,
+    pub life_stage: usize,
+    pub encountered: usize,
+    pub recent_domain: usize,
+    pub candidates: usize,
+}
+
+/// What one procedural generation attempt saw and decided
+#[derive(Debug, Clone, Default)]
+pub struct AttemptTrace {
+    pub wildcard: bool,
+    pub filtered: FilterCounts,
+    /// Id of the situation drawn from the candidates
+    pub selected: Option<String>,
+    /// Why the attempt produced no event
+    pub failure: Option<String>,
+}
+
+impl AttemptTrace {
+    /// One line, e.g. "47 situations, 30 filtered by tier, ..., selected X"
+    pub fn summary(&self) -> String {
+        let counts = &self.filtered;
+        let mut summary = format!("{} situations, ", counts.total);
+        if counts.draft > 0 {
+            summary.push_str(&format!("{} drafts skipped, ", counts.draft));
+        }
+        summary.push_str(&format!(
+            "{} filtered by tier, {} by life stage, {} already seen, ",
+            counts.tier, counts.life_stage, counts.encountered
+        ));
+        if self.wildcard {
+            summary.push_str("domain filter skipped (wildcard), ");
+        } else {
+            summary.push_str(&format!("{} by domain, ", counts.recent_domain));
+        }
+        summary.push_str(&format!("{} candidates", counts.candidates));
+        if let Some(id) = &self.selected {
+            summary.push_str(&format!(", selected {}", id));
+        }
+        if let Some(failure) = &self.failure {
+            summary.push_str(&format!(": {}", failure));
+        }
+        summary
+    }
+}
 
 /// Filter situations based on player state and context with detailed logging
 fn filter_situations<'a>(
-    situations: &'a [&'a SituationTemplate],
+    situations: &'a [SituationTemplate],
     player_tier: usize,
     life_stage: usize,
     recent_domains: &VecDeque<EventDomain>,
-    encounter_history: &std::collections::HashSet<String>,
+    encounter_map: &HashMap<String, u64>,
     allow_wildcard: bool,
-) -> Vec<&'a SituationTemplate> {
+    include_drafts: bool,
+) -> (Vec<&'a SituationTemplate>, FilterCounts) {
     let total_situations = situations.len();
     log::debug!("Starting situation filtering with {} total situations", total_situations);
     log::debug!("  Player state: tier={}, life_stage={}", player_tier, life_stage);
     log::debug!("  Wildcard mode: {}", allow_wildcard);
 
-    let last_two_domains: Vec<&EventDomain> = recent_domains.iter().take(2).collect();
+    // The deque is pushed at the back, so the most recent domains are at the end
+    let last_two_domains: Vec<&EventDomain> = recent_domains
+        .iter()
+        .rev()
+        .take(RECENT_DOMAIN_FILTER_SIZE)
+        .collect();
     if !last_two_domains.is_empty() {
         log::debug!("  Recent domains (last 2): {:?}", last_two_domains.iter().map(|d| d.as_str()).collect::<Vec<_>>());
     }
 
+    let mut draft_filtered = 0;
     let mut tier_filtered = 0;
     let mut stage_filtered = 0;
     let mut encountered_filtered = 0;
     let mut domain_filtered = 0;
+    let placement = placement_range(player_tier);
 
     let filtered: Vec<&'a SituationTemplate> = situations
         .iter()
-        .copied()
         .filter(|s| {
-            // Tier filter: player_tier ± 1
-            let tier_ok = s.tier_min <= player_tier.saturating_add(1)
-                && s.tier_max >= player_tier.saturating_sub(1);
+            // Unfinished content stays out of play
+            if s.draft && !include_drafts {
+                log::trace!("  FILTERED (draft): {}", s.id);
+                draft_filtered += 1;
+                return false;
+            }
+
+            // Tier filter: player_tier ± TIER_PLACEMENT_TOLERANCE, on the board
+            let tier_ok = s.tier_min <= *placement.end() && s.tier_max >= *placement.start();
             if !tier_ok {
                 log::trace!("  FILTERED (tier): {} - tier_range=({}-{}), player_tier={}",
                     s.id, s.tier_min, s.tier_max, player_tier);
@@ -57,7 +138,7 @@ fn filter_situations<'a>(
             }
 
             // Encounter history filter
-            let not_encountered = !encounter_history.contains(&s.id);
+            let not_encountered = !encounter_map.contains_key(&s.id);
             if !not_encountered {
                 log::trace!("  FILTERED (already_encountered): {}", s.id);
                 encountered_filtered += 1;
@@ -80,89 +161,204 @@ fn filter_situations<'a>(
 
     log::info!("Situation filtering complete:");
     log::info!("  Total situations: {}", total_situations);
+    log::info!("  Filtered as drafts: {}", draft_filtered);
     log::info!("  Filtered by tier: {}", tier_filtered);
     log::info!("  Filtered by life_stage: {}", stage_filtered);
-    log::info!("  Filtered by encounter_history: {}", encountered_filtered);
+    log::info!("  Filtered as recently encountered: {}", encountered_filtered);
     log::info!("  Filtered by recent_domain: {}", domain_filtered);
     log::info!("  Remaining candidates: {}", filtered.len());
 
-    filtered
+    let counts = FilterCounts {
+        total: total_situations,
+        draft: draft_filtered,
+        tier: tier_filtered,
+        life_stage: stage_filtered,
+        encountered: encountered_filtered,
+        recent_domain: domain_filtered,
+        candidates: filtered.len(),
+    };
+    (filtered, counts)
 }
 
-/// Check if player meets requirements for a choice
-fn player_meets_requirements(
-    player_state: &LotusApp,
-    requirements: &std::collections::HashMap<String, u32>,
-) -> bool {
-    for (key, &required_value) in requirements {
-        let player_value = match key.as_str() {
-            "guanxi_family" => player_state.guanxi_family,
-            "guanxi_network" => player_state.guanxi_network,
-            "guanxi_party" => player_state.guanxi_party,
-            "career_level" => player_state.career_level,
-            _ => 0,
-        };
+/// Which outcome a result line describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResultKind {
+    Rewarded, // Success that raised SCS
+    Costly,   // Success with a price
+    Failed,
+}
 
-        if player_value < required_value {
-            return false;
-        }
+/// Result line templates per archetype and outcome
+fn result_templates(archetype: &ChoiceType, kind: ResultKind) -> &'static [&'static str] {
+    match (archetype, kind) {
+        (ChoiceType::Conform, ResultKind::Rewarded) => &[
+            "You chose to conform. Things went well.",
+            "You went along with it, and someone important noticed.",
+            "Playing by the rules paid off this time.",
+        ],
+        (ChoiceType::Conform, ResultKind::Costly) => &[
+            "You chose to conform. There were consequences.",
+            "You went along with it, though it cost you.",
+            "Compliance kept you safe, but not for free.",
+        ],
+        (ChoiceType::Conform, ResultKind::Failed) => &[
+            "You chose to conform, but it backfired. Things didn't go as planned.",
+            "Going along with it didn't protect you this time.",
+            "Your obedience was noted, and still held against you.",
+        ],
+        (ChoiceType::Resist, ResultKind::Rewarded) => &[
+            "You chose to resist. Things went well.",
+            "You stood your ground, and it worked.",
+            "Pushing back earned you more respect than you expected.",
+        ],
+        (ChoiceType::Resist, ResultKind::Costly) => &[
+            "You chose to resist. There were consequences.",
+            "You stood your ground, and paid for it.",
+            "Pushing back made your point, and a few enemies.",
+        ],
+        (ChoiceType::Resist, ResultKind::Failed) => &[
+            "You chose to resist, but it backfired. Things didn't go as planned.",
+            "Your defiance was swiftly put down.",
+            "You pushed back, and the system pushed harder.",
+        ],
+        (ChoiceType::Manipulate, ResultKind::Rewarded) => &[
+            "You chose to manipulate. Things went well.",
+            "Your maneuvering worked exactly as planned.",
+            "A few quiet words in the right ears did the trick.",
+        ],
+        (ChoiceType::Manipulate, ResultKind::Costly) => &[
+            "You chose to manipulate. There were consequences.",
+            "Your scheme worked, but someone will want a favor back.",
+            "You got your way, though it took more than you hoped.",
+        ],
+        (ChoiceType::Manipulate, ResultKind::Failed) => &[
+            "You chose to manipulate, but it backfired. Things didn't go as planned.",
+            "Your scheme unraveled in front of the wrong people.",
+            "Someone saw through your maneuvering.",
+        ],
+        (ChoiceType::Ignore, ResultKind::Rewarded) => &[
+            "You chose to ignore it. Things went well.",
+            "Keeping your head down was the right call.",
+            "The matter passed without touching you.",
+        ],
+        (ChoiceType::Ignore, ResultKind::Costly) => &[
+            "You chose to ignore it. There were consequences.",
+            "Looking away didn't make the problem disappear.",
+            "Your silence was noticed, and not kindly.",
+        ],
+        (ChoiceType::Ignore, ResultKind::Failed) => &[
+            "You chose to ignore it, but it backfired. Things didn't go as planned.",
+            "The problem you ignored came looking for you.",
+            "Staying out of it turned out to be a choice too.",
+        ],
+    }
+}
+
+/// Picks a result line for the outcome, skipping any the player has seen
+/// recently unless every line in the pool has been
+fn pick_result_text(
+    player_state: &LotusApp,
+    choice: &ChoiceArchetype,
+    kind: ResultKind,
+    context: &mut SubstitutionContext,
+    rng: &mut impl Rng,
+) -> String {
+    let authored = match kind {
+        ResultKind::Rewarded | ResultKind::Costly => &choice.success_text,
+        ResultKind::Failed => &choice.failure_text,
+    };
+    let templates: Vec<&str> = if authored.is_empty() {
+        result_templates(&choice.archetype, kind).to_vec()
+    } else {
+        authored.iter().map(String::as_str).collect()
+    };
+    let pool: Vec<String> = templates
+        .iter()
+        .map(|template| resolve_placeholders(template, context, rng))
+        .collect();
+    let fresh: Vec<&String> = pool
+        .iter()
+        .filter(|text| !player_state.recent_results.contains(*text))
+        .collect();
+    match fresh.choose(rng) {
+        Some(text) => (*text).clone(),
+        None => pool.choose(rng).cloned().unwrap_or_default(),
     }
-    true
 }
 
-/// Generate a procedural event based on player state
-pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) -> Option<EventData> {
+/// Generate a procedural event based on player state, for a player standing
+/// at `player_tier` and `life_stage`, recording the decisions made along the
+/// way in `trace`. The situation drawn must offer at least `min_options`
+/// choices the player qualifies for.
+pub fn generate_procedural_event(
+    player_state: &LotusApp,
+    player_tier: usize,
+    life_stage: usize,
+    rng: &mut impl Rng,
+    min_options: usize,
+    trace: &mut AttemptTrace,
+) -> Option<EventData> {
     log::info!("=== PROCEDURAL EVENT GENERATION ATTEMPT ===");
 
     let library = &player_state.situation_library;
 
     // 10% wildcard probability: ignore domain filter
     let allow_wildcard = rng.random_bool(0.1);
+    trace.wildcard = allow_wildcard;
     if allow_wildcard {
         log::info!("WILDCARD mode activated - ignoring recent domain filter");
     }
 
-    // Collect all situations from all domains
-    let all_situations: Vec<&SituationTemplate> = library
-        .by_domain
-        .values()
-        .flat_map(|situations| situations.iter())
-        .collect();
-
-    log::debug!("Total situations in library: {}", all_situations.len());
+    log::debug!("Total situations in library: {}", library.situations().len());
 
     // Filter situations based on player state and context
-    let candidates = filter_situations(
-        &all_situations,
-        player_state.player_tier,
-        player_state.life_stage,
-        &player_state.recent_event_domains,
-        &player_state.encounter_history,
+    let (candidates, counts) = filter_situations(
+        library.situations(),
+        player_tier,
+        life_stage,
+        player_state.recent_domains(),
+        &player_state.encounter_map,
         allow_wildcard,
+        player_state.drafts_enabled(),
     );
+    trace.filtered = counts;
 
     if candidates.is_empty() {
+        trace.failure = Some("no candidate situations".to_string());
         log::warn!("PROCEDURAL GENERATION FAILED: No candidate situations after filtering");
         log::warn!("  Reason: All situations filtered out by tier/stage/history/domain criteria");
         log::warn!("  Will fall back to handcrafted events");
         return None;
     }
 
+    // Pacing: hold back High severity situations for a few turns after one
+    // resolves. If nothing else is left, a handcrafted event fills the gap.
+    let hold_high = player_state.high_severity_on_cooldown();
+    if hold_high {
+        log::debug!("High severity situations held back by pacing");
+        if candidates.iter().all(|s| s.severity == Severity::High) {
+            log::info!("PROCEDURAL GENERATION SKIPPED: only High severity candidates while paced");
+            trace.failure = Some("only High severity candidates while paced".to_string());
+            return None;
+        }
+    }
+
     // Weighted selection: prefer exact tier/stage matches
     let weights: Vec<f32> = candidates
         .iter()
         .map(|s| {
+            if hold_high && s.severity == Severity::High {
+                return 0.0;
+            }
             let mut weight = 1.0;
 
             // Bonus for exact tier match
-            if s.tier_min <= player_state.player_tier && s.tier_max >= player_state.player_tier {
+            if s.tier_min <= player_tier && s.tier_max >= player_tier {
                 weight *= 2.0;
             }
 
             // Bonus for exact stage match
-            if s.life_stage_min <= player_state.life_stage
-                && s.life_stage_max >= player_state.life_stage
-            {
+            if s.life_stage_min <= life_stage && s.life_stage_max >= life_stage {
                 weight *= 2.0;
             }
 
@@ -171,8 +367,12 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
         .collect();
 
     // Weighted random selection
-    let dist = WeightedIndex::new(&weights).ok()?;
+    let Ok(dist) = WeightedIndex::new(&weights) else {
+        trace.failure = Some("no candidate has any weight".to_string());
+        return None;
+    };
     let selected_situation = candidates[dist.sample(rng)];
+    trace.selected = Some(selected_situation.id.clone());
 
     log::info!("Selected situation: '{}' (domain={}, tier={}-{}, stage={}-{})",
         selected_situation.id,
@@ -183,19 +383,51 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
         selected_situation.life_stage_max
     );
 
-    // Generate event description
-    let description = assemble_description(
-        &selected_situation.fragments,
+    let position = (player_tier, life_stage);
+    assemble_event(player_state, selected_situation, position, rng, min_options, trace)
+}
+
+/// Builds an event from `situation` for a player at `(tier, life_stage)`, or
+/// None (with the reason in `trace`) if it offers fewer than `min_options`
+/// choices the player qualifies for or its text fails to assemble
+fn assemble_event(
+    player_state: &LotusApp,
+    situation: &SituationTemplate,
+    (tier, life_stage): (usize, usize),
+    rng: &mut impl Rng,
+    min_options: usize,
+    trace: &mut AttemptTrace,
+) -> Option<EventData> {
+    let library = &player_state.situation_library;
+
+    // Generate event description. Variables it resolves are reused by the
+    // choices and results below.
+    let time = player_state.time_context();
+    let mut context = SubstitutionContext::new(
         &library.variables,
-        player_state.player_tier,
+        tier,
+        &player_state.player_identity,
+    )
+    .with_time(time)
+    .with_stats(PlayerStats::from(player_state));
+    let description = assemble_description(
+        &situation.id,
+        &situation.fragments,
+        recall(&player_state.choice_memory, situation.domain),
+        &mut context,
         rng,
-    );
+    )
+    .inspect_err(|e| {
+        log::error!("PROCEDURAL GENERATION FAILED: {}", e);
+        trace.failure = Some(e.to_string());
+    })
+    .ok()?;
 
     // Generate title from domain and severity
     let title = format!(
         "{} - {} Severity",
-        selected_situation.domain.as_str(),
-        match selected_situation.severity {
+        situation.domain.as_str(),
+        match situation.severity {
             super::library::Severity::Low => "Low",
             super::library::Severity::Medium => "Medium",
             super::library::Severity::High => "High",
@@ -203,14 +435,16 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
     );
 
     // Assemble choices - filter by requirements
-    let total_choices = selected_situation.choices.len();
+    let total_choices = situation.choices.len();
     log::debug!("Filtering {} choices by player requirements", total_choices);
 
-    let available_choices: Vec<&ChoiceArchetype> = selected_situation
+    let player_stats = PlayerStats::from(player_state);
+
+    let available_choices: Vec<&ChoiceArchetype> = situation
         .choices
         .iter()
         .filter(|c| {
-            let meets_reqs = player_meets_requirements(player_state, &c.requirements);
+            let meets_reqs = player_stats.meets(&c.requirements);
             if !meets_reqs {
                 log::debug!("  Choice '{}' filtered - requirements not met: {:?}",
                     c.archetype.as_str(), c.requirements);
@@ -219,12 +453,31 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
         })
         .collect();
 
+    // Drop choices made in this situation within the cooldown window, but
+    // keep them all if that would leave nothing to choose
+    let off_cooldown: Vec<&ChoiceArchetype> = available_choices
+        .iter()
+        .copied()
+        .filter(|c| {
+            let on_cooldown = player_state.choice_on_cooldown(&situation.id, c.archetype);
+            if on_cooldown {
+                log::debug!("  Choice '{}' filtered - on cooldown", c.archetype.as_str());
+            }
+            !on_cooldown
+        })
+        .collect();
+    let available_choices = if off_cooldown.is_empty() {
+        available_choices
+    } else {
+        off_cooldown
+    };
+
     log::info!("Available choices: {}/{}", available_choices.len(), total_choices);
 
     // Must have at least one available choice
     if available_choices.is_empty() {
         log::warn!("PROCEDURAL GENERATION FAILED: No available choices");
-        log::warn!("  Situation: '{}'", selected_situation.id);
+        log::warn!("  Situation: '{}'", situation.id);
         log::warn!("  Reason: All {} choices filtered by requirement checks", total_choices);
         log::warn!("  Player stats: career={}, family={}, network={}, party={}",
             player_state.career_level,
@@ -233,62 +486,84 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
             player_state.guanxi_party
         );
         log::warn!("  Will fall back to handcrafted events");
+        trace.failure = Some("no choices the player qualifies for".to_string());
+        return None;
+    }
+    if available_choices.len() < min_options {
+        log::info!(
+            "PROCEDURAL GENERATION REJECTED: '{}' offers {} of the {} choices required",
+            situation.id,
+            available_choices.len(),
+            min_options
+        );
+        trace.failure = Some(format!(
+            "only {} of {} required choices available",
+            available_choices.len(),
+            min_options
+        ));
         return None;
     }
 
     // Build EventOptions from available choices
-    let options: Vec<EventOption> = available_choices
+    let options: Option<Vec<EventOption>> = available_choices
         .iter()
         .map(|choice| {
             // Generate choice text
-            let text = assemble_choice_text(&choice.text_fragments, rng);
+            let text = assemble_choice_text(
+                &situation.id,
+                &choice.text_fragments,
+                &mut context,
+                rng,
+            )
+            .inspect_err(|e| log::error!("PROCEDURAL GENERATION FAILED: {}", e))
+            .ok()?;
 
             // Calculate context-driven stats
             let success_stats = calculate_stats(
                 &choice.base_stats,
-                player_state.player_tier,
-                selected_situation.severity,
+                tier,
+                situation.severity,
                 rng,
             );
 
             // Calculate failure stats (inverted/amplified)
             let failure_stats = calculate_failure_stats(&success_stats);
 
-            // Calculate risk
-            let player_stats = PlayerStats {
-                guanxi_family: player_state.guanxi_family,
-                guanxi_network: player_state.guanxi_network,
-                guanxi_party: player_state.guanxi_party,
-                career_level: player_state.career_level,
-            };
-
-            let risk_chance = calculate_risk(
-                selected_situation.base_risk,
-                choice.risk_modifier,
+            // Calculate risk. Scheming is harder to hide after hours.
+            let mut risk_modifier = choice.risk_modifier;
+            if choice.archetype == ChoiceType::Manipulate && time.after_hours() {
+                risk_modifier =
+                    risk_modifier.saturating_add(player_state.config.after_hours_manipulate_risk);
+            }
+            let risk = explain_risk(
+                situation.base_risk,
+                risk_modifier,
                 &choice.requirements,
                 &player_stats,
+                tier,
+                player_state
+                    .config
+                    .tier_risk_curves
+                    .get(&situation.domain)
+                    .map_or(&[], Vec::as_slice),
+                player_state.config.risk_clamp(situation.domain),
             );
 
-            // Generate result text
-            let success_result = format!(
-                "You chose to {}. {}",
-                choice.archetype.as_str(),
-                if success_stats.scs_change > 0 {
-                    "Things went well."
-                } else {
-                    "There were consequences."
-                }
-            );
-
-            let failure_result = format!(
-                "You chose to {}, but it backfired. Things didn't go as planned.",
-                choice.archetype.as_str()
-            );
+            // Generate result text, avoiding lines the player has just seen
+            let success_kind = if success_stats.scs_change > 0 {
+                ResultKind::Rewarded
+            } else {
+                ResultKind::Costly
+            };
+            let success_result =
+                pick_result_text(player_state, choice, success_kind, &mut context, rng);
+            let failure_result =
+                pick_result_text(player_state, choice, ResultKind::Failed, &mut context, rng);
 
-            EventOption {
+            Some(EventOption {
                 text,
                 requirements: choice.requirements.clone(),
-                risk_chance,
+                risk_chance: risk.risk,
                 success_outcome: EventOutcome {
                     scs_change: success_stats.scs_change,
                     finance_change: success_stats.finance_change,
@@ -296,6 +571,7 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
                     guanxi_family_change: success_stats.guanxi_family_change,
                     guanxi_network_change: success_stats.guanxi_network_change,
                     guanxi_party_change: success_stats.guanxi_party_change,
+                    ..Default::default()
                 },
                 success_result,
                 failure_outcome: Some(EventOutcome {
@@ -305,26 +581,204 @@ pub fn generate_procedural_event(player_state: &LotusApp, rng: &mut impl Rng) ->
                     guanxi_family_change: failure_stats.guanxi_family_change,
                     guanxi_network_change: failure_stats.guanxi_network_change,
                     guanxi_party_change: failure_stats.guanxi_party_change,
+                    ..Default::default()
                 }),
                 failure_result,
-            }
+                archetype: Some(choice.archetype),
+                risk_breakdown: Some(risk),
+                notes: String::new(),
+                is_skip: false,
+            })
         })
         .collect();
+    let Some(mut options) = options else {
+        trace.failure = Some("choice text failed to assemble".to_string());
+        return None;
+    };
+    if player_state.config.shuffle_options {
+        options.shuffle(rng);
+    }
+    if player_state.config.skip_option_enabled && !situation.no_skip {
+        options.push(skip_option(&player_state.config));
+    }
 
     log::info!("✓ PROCEDURAL EVENT GENERATION SUCCEEDED");
-    log::info!("  Event: '{}' from domain '{}'", title, selected_situation.domain.as_str());
-    log::info!("  Situation ID: '{}'", selected_situation.id);
+    log::info!("  Event: '{}' from domain '{}'", title, situation.domain.as_str());
+    log::info!("  Situation ID: '{}'", situation.id);
     log::info!("  Options available: {}", options.len());
 
-    Some(EventData {
+    let event = EventData {
         title,
-        description,
+        description: description.text,
+        description_parts: description.parts,
+        notes: String::new(),
         options,
-        min_tier: selected_situation.tier_min,
-        max_tier: selected_situation.tier_max,
+        min_tier: situation.tier_min,
+        max_tier: situation.tier_max,
         is_generic: false,
-        life_stage: player_state.life_stage,
-        procedural_id: Some(selected_situation.id.clone()),
-        procedural_domain: Some(selected_situation.domain.as_str().to_string()),
-    })
+        life_stage,
+        icon: situation.icon.clone(),
+        procedural_id: Some(situation.id.clone()),
+        procedural_domain: Some(situation.domain.as_str().to_string()),
+    };
+    event
+        .validate()
+        .inspect_err(|e| {
+            log::error!("PROCEDURAL GENERATION FAILED: {}", e);
+            trace.failure = Some(e.to_string());
+        })
+        .ok()?;
+    Some(event)
+}
+
+/// Assembles `count` events from one situation, skipping selection, for
+/// previewing the range of text it produces. The player's identity, stats and
+/// memory fill it in as if they stood at `tier`. The same seed gives the same
+/// events; fails with the reason if any of them can't be built.
+pub fn generate_samples(
+    player_state: &LotusApp,
+    situation_id: &str,
+    tier: usize,
+    count: usize,
+    seed: u64,
+) -> anyhow::Result<Vec<EventData>> {
+    let situation = player_state
+        .situation_library
+        .situation_by_id(situation_id)
+        .ok_or_else(|| anyhow::anyhow!("unknown situation '{}'", situation_id))?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            let mut trace = AttemptTrace::default();
+            let position = (tier, player_state.life_stage);
+            assemble_event(player_state, situation, position, &mut rng, 1, &mut trace).ok_or_else(
+                || anyhow::anyhow!(trace.failure.unwrap_or_else(|| "no event".to_string())),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::RECENT_DOMAIN_WINDOW;
+    use crate::config::GameConfig;
+    use crate::fixture::{Fixture, TEST_SEED};
+    use crate::game_data::generate_event;
+    use crate::procedural::SituationLibrary;
+    use anyhow::Result;
+
+    #[test]
+    fn placement_stays_on_the_board() {
+        assert_eq!(placement_range(0), 0..=1);
+        assert_eq!(placement_range(TIER_COUNT - 1), TIER_COUNT - 2..=TIER_COUNT - 1);
+        assert_eq!(placement_range(TIER_COUNT + 3), TIER_COUNT - 2..=TIER_COUNT - 1);
+    }
+
+    #[test]
+    fn drafts_are_filtered_unless_included() -> Result<()> {
+        let mut situations = SituationLibrary::from_embedded_configs()?.situations().to_vec();
+        for situation in &mut situations {
+            situation.draft = true;
+        }
+        let filter = |include_drafts| {
+            let (candidates, counts) = filter_situations(
+                &situations,
+                2,
+                2,
+                &VecDeque::new(),
+                &HashMap::new(),
+                false,
+                include_drafts,
+            );
+            (candidates.len(), counts.draft)
+        };
+        assert_eq!(filter(false), (0, situations.len()));
+        assert!(filter(true).0 > 0);
+        Ok(())
+    }
+
+    /// Resolved procedural events fill the recent-domain window in order,
+    /// and only the newest `RECENT_DOMAIN_FILTER_SIZE` are excluded unless
+    /// a wildcard ignores them
+    #[test]
+    fn recent_domains_are_filtered() -> Result<()> {
+        let mut config = GameConfig::from_embedded()?;
+        config.rng_seed = Some(TEST_SEED);
+        let mut app = Fixture { config, ..Fixture::new()? }.build()?;
+        let mut resolved = Vec::new();
+        while resolved.len() < RECENT_DOMAIN_WINDOW + 3 {
+            let event = generate_event(&app)?;
+            let Some(domain) = event.domain() else {
+                continue;
+            };
+            app.current_event = Some(event);
+            app.resolve_option(0, false).expect("option 0 should resolve");
+            resolved.push(domain);
+        }
+        let window = &resolved[resolved.len() - RECENT_DOMAIN_WINDOW..];
+        assert!(app.recent_domains().iter().eq(window));
+
+        let filter = |allow_wildcard| {
+            filter_situations(
+                app.situation_library.situations(),
+                2,
+                2,
+                app.recent_domains(),
+                &HashMap::new(),
+                allow_wildcard,
+                false,
+            )
+        };
+        let newest = &window[window.len() - RECENT_DOMAIN_FILTER_SIZE..];
+        let (candidates, counts) = filter(false);
+        assert!(counts.recent_domain > 0);
+        assert!(candidates.iter().all(|s| !newest.contains(&s.domain)));
+        assert!(!candidates.is_empty());
+
+        let (candidates, counts) = filter(true);
+        assert_eq!(counts.recent_domain, 0);
+        assert!(candidates.iter().any(|s| newest.contains(&s.domain)));
+        Ok(())
+    }
+
+    /// Batches come from the named situation and repeat for the same seed
+    #[test]
+    fn samples_repeat_for_a_seed() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        // Qualify for every choice so no sample fails on requirements
+        app.career_level = u32::MAX;
+        app.guanxi_family = u32::MAX;
+        app.guanxi_network = u32::MAX;
+        app.guanxi_party = u32::MAX;
+        for situation in app.situation_library.situations() {
+            let draw = || generate_samples(&app, &situation.id, situation.tier_min, 5, TEST_SEED);
+            let first = draw()?;
+            assert_eq!(first.len(), 5);
+            for event in &first {
+                assert_eq!(event.procedural_id.as_ref(), Some(&situation.id));
+            }
+            let descriptions = |events: Vec<EventData>| -> Vec<String> {
+                events.into_iter().map(|e| e.description).collect()
+            };
+            assert_eq!(descriptions(first), descriptions(draw()?), "{}", situation.id);
+        }
+        assert!(generate_samples(&app, "no_such_situation", 0, 1, TEST_SEED).is_err());
+        Ok(())
+    }
+
+    /// Every generated choice explains the risk it actually rolls against
+    #[test]
+    fn breakdowns_match_the_rolled_risk() -> Result<()> {
+        let app = Fixture::new()?.build()?;
+        for situation in app.situation_library.situations() {
+            for event in generate_samples(&app, &situation.id, situation.tier_min, 3, TEST_SEED)? {
+                for option in event.options.iter().filter(|o| o.archetype.is_some()) {
+                    let breakdown = option.risk_breakdown.as_ref().expect("no risk breakdown");
+                    assert_eq!(breakdown.risk, option.risk_chance, "{}", option.text);
+                }
+            }
+        }
+        Ok(())
+    }
 }
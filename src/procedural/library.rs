@@ -1,6 +1,12 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, ensure};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::TIER_COUNT;
+use crate::config::RequirementCeiling;
+use crate::requirements::{RequirementKey, Requirements};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -19,7 +25,7 @@ pub enum Severity {
     High,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChoiceType {
     Conform,
@@ -44,23 +50,96 @@ pub struct StatProfile {
     pub guanxi_party_change: i32,
 }
 
+/// A single narrative fragment. In TOML it can be a plain string (weight 1)
+/// or a table like `{ text = "...", weight = 3, tags = ["grim"] }`.
+/// Tags keep the conflict/stakes picks in the same tone as the opening.
+/// `requires = { guanxi_party = 1 }` offers the fragment only to players
+/// meeting those minimums, like a choice's requirements.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "FragmentConfig")]
+pub struct Fragment {
+    pub text: String,
+    pub weight: u32,
+    pub tags: Vec<String>,
+    pub requires: Requirements,
+}
+
+impl Fragment {
+    /// True if both fragments carry at least one tag in common
+    pub fn shares_tag_with(&self, other: &Fragment) -> bool {
+        self.tags.iter().any(|tag| other.tags.contains(tag))
+    }
+}
+
+// Helper enum so plain string lists keep deserializing
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FragmentConfig {
+    Plain(String),
+    Table {
+        text: String,
+        #[serde(default = "default_fragment_weight")]
+        weight: u32,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        requires: Requirements,
+    },
+}
+
+fn default_fragment_weight() -> u32 {
+    1
+}
+
+impl From<FragmentConfig> for Fragment {
+    fn from(config: FragmentConfig) -> Self {
+        match config {
+            FragmentConfig::Plain(text) => Fragment {
+                text,
+                weight: 1,
+                tags: Vec::new(),
+                requires: Requirements::new(),
+            },
+            FragmentConfig::Table {
+                text,
+                weight,
+                tags,
+                requires,
+            } => Fragment {
+                text,
+                weight,
+                tags,
+                requires,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NarrativeFragments {
-    pub openings: Vec<String>,
-    pub conflicts: Vec<String>,
-    pub stakes: Vec<String>,
+    pub openings: Vec<Fragment>,
+    pub conflicts: Vec<Fragment>,
+    pub stakes: Vec<Fragment>,
+    /// Optional closing lines recalling a past decision via `{past_choice}`
+    #[serde(default)]
+    pub callbacks: Vec<Fragment>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChoiceArchetype {
     pub archetype: ChoiceType,
     pub text_fragments: Vec<String>,
+    /// Authored result lines; the generic archetype lines are used when empty
+    #[serde(default)]
+    pub success_text: Vec<String>,
+    #[serde(default)]
+    pub failure_text: Vec<String>,
     #[serde(flatten)]
     pub base_stats: StatProfile,
     #[serde(default)]
     pub risk_modifier: i8,
     #[serde(default)]
-    pub requirements: HashMap<String, u32>,
+    pub requirements: Requirements,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -75,6 +154,18 @@ pub struct SituationTemplate {
     pub base_risk: u8,
     pub fragments: NarrativeFragments,
     pub choices: Vec<ChoiceArchetype>,
+    /// Opts out of the synthetic "Do nothing" option
+    #[serde(default)]
+    pub no_skip: bool,
+    /// Author notes, shown in the content browser and never in play
+    #[serde(default)]
+    pub notes: String,
+    /// Unfinished; kept out of generation unless a debug build opts in
+    #[serde(default)]
+    pub draft: bool,
+    /// Embedded icon name or PNG path for the event modal header
+    #[serde(default)]
+    pub icon: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -201,21 +292,56 @@ pub struct VariableLibraries {
     pub survey_topic: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// The situations and variables procedural events are built from. The
+/// situation list and its indices are only built together, by `new`.
+#[derive(Debug, Clone)]
 pub struct SituationLibrary {
-    pub by_domain: HashMap<EventDomain, Vec<SituationTemplate>>,
+    /// Every situation, flattened once at load in a stable domain order
+    situations: Vec<SituationTemplate>,
+    /// Indices into `situations`, grouped by domain
+    by_domain: HashMap<EventDomain, Vec<usize>>,
+    /// Index into `situations` by situation id
+    id_index: HashMap<String, usize>,
     pub variables: VariableLibraries,
 }
 
+/// The situation and variable files a procedural content directory holds
+pub const CONTENT_FILES: [&str; 5] = [
+    "work_events.toml",
+    "family_events.toml",
+    "public_events.toml",
+    "party_events.toml",
+    "variables.toml",
+];
+
 impl SituationLibrary {
     pub fn from_embedded_configs() -> Result<Self> {
-        // Load embedded TOML files
-        let work_toml = include_str!("../../data/procedural/work_events.toml");
-        let family_toml = include_str!("../../data/procedural/family_events.toml");
-        let public_toml = include_str!("../../data/procedural/public_events.toml");
-        let party_toml = include_str!("../../data/procedural/party_events.toml");
-        let variables_toml = include_str!("../../data/procedural/variables.toml");
+        Self::from_tomls([
+            include_str!("../../data/procedural/work_events.toml"),
+            include_str!("../../data/procedural/family_events.toml"),
+            include_str!("../../data/procedural/public_events.toml"),
+            include_str!("../../data/procedural/party_events.toml"),
+            include_str!("../../data/procedural/variables.toml"),
+        ])
+    }
 
+    /// Loads the `CONTENT_FILES` from `dir` instead of the embedded copies
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let texts = CONTENT_FILES
+            .map(|name| {
+                let path = dir.join(name);
+                fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))
+            })
+            .into_iter()
+            .collect::<Result<Vec<String>>>()?;
+        Self::from_tomls(std::array::from_fn(|i| texts[i].as_str()))
+    }
+
+    /// Parses the `CONTENT_FILES`, in that order
+    fn from_tomls(
+        [work_toml, family_toml, public_toml, party_toml, variables_toml]: [&str; 5],
+    ) -> Result<Self> {
         // Parse situations
         let work_config: SituationConfig =
             toml::from_str(work_toml).context("Failed to parse work_events.toml")?;
@@ -366,18 +492,186 @@ impl SituationLibrary {
             log::info!("✓ All variable lists loaded successfully!");
         }
 
-        // Build by_domain HashMap
-        let mut by_domain = HashMap::new();
-        by_domain.insert(EventDomain::Work, work_config.situations);
-        by_domain.insert(EventDomain::Family, family_config.situations);
-        by_domain.insert(EventDomain::Public, public_config.situations);
-        by_domain.insert(EventDomain::Party, party_config.situations);
+        let situations = [work_config, family_config, public_config, party_config]
+            .into_iter()
+            .flat_map(|config| config.situations)
+            .collect();
+        let library = Self::new(situations, variables)?;
+        for situation in library.situations.iter().filter(|situation| situation.draft) {
+            log::warn!("DRAFT situation '{}' ({})", situation.id, situation.notes);
+        }
+        Ok(library)
+    }
 
+    /// Indexes `situations` by domain and by id, keeping their order. Fails
+    /// if two situations share an id.
+    pub fn new(situations: Vec<SituationTemplate>, variables: VariableLibraries) -> Result<Self> {
+        let mut by_domain: HashMap<EventDomain, Vec<usize>> = HashMap::new();
+        let mut id_index = HashMap::new();
+        for (i, situation) in situations.iter().enumerate() {
+            by_domain.entry(situation.domain).or_default().push(i);
+            ensure!(
+                id_index.insert(situation.id.clone(), i).is_none(),
+                "situation id '{}' is used more than once",
+                situation.id
+            );
+        }
         Ok(Self {
+            situations,
             by_domain,
+            id_index,
             variables,
         })
     }
+
+    /// Fails if any situation's life stage range is empty or reaches past
+    /// `stage_count`
+    pub fn check_life_stages(&self, stage_count: usize) -> Result<()> {
+        for situation in &self.situations {
+            ensure!(
+                1 <= situation.life_stage_min
+                    && situation.life_stage_min <= situation.life_stage_max
+                    && situation.life_stage_max <= stage_count,
+                "situation '{}' covers life stages {}-{}, but only 1-{} exist",
+                situation.id,
+                situation.life_stage_min,
+                situation.life_stage_max,
+                stage_count
+            );
+        }
+        Ok(())
+    }
+
+    /// Fails if any situation's tier range is empty or reaches past the board
+    pub fn check_tiers(&self) -> Result<()> {
+        for situation in &self.situations {
+            ensure!(
+                situation.tier_min <= situation.tier_max && situation.tier_max < TIER_COUNT,
+                "situation '{}' covers tiers {}-{}, but only 0-{} exist",
+                situation.id,
+                situation.tier_min,
+                situation.tier_max,
+                TIER_COUNT - 1
+            );
+        }
+        Ok(())
+    }
+
+    /// Pairs of situations in the same domain whose authored text overlaps at
+    /// least `threshold` (0-1, see `SituationTemplate::similarity`), with
+    /// their similarity. Nothing is changed; this only points authors at
+    /// likely copy-paste duplicates.
+    pub fn likely_duplicates(&self, threshold: f32) -> Vec<(&str, &str, f32)> {
+        let mut pairs = Vec::new();
+        for indices in self.by_domain.values() {
+            for (n, &a) in indices.iter().enumerate() {
+                for &b in &indices[n + 1..] {
+                    let (first, second) = (&self.situations[a], &self.situations[b]);
+                    let similarity = first.similarity(second);
+                    if similarity >= threshold {
+                        pairs.push((first.id.as_str(), second.id.as_str(), similarity));
+                    }
+                }
+            }
+        }
+        pairs.sort_by(|x, y| (x.0, x.1).cmp(&(y.0, y.1)));
+        pairs
+    }
+
+    /// Logs `likely_duplicates` as warnings
+    pub fn warn_duplicates(&self, threshold: f32) {
+        for (first, second, similarity) in self.likely_duplicates(threshold) {
+            log::warn!(
+                "Situations '{}' and '{}' look like duplicates ({:.0}% of their text is shared)",
+                first,
+                second,
+                similarity * 100.0
+            );
+        }
+    }
+
+    /// Requirements above `ceiling` for the situation's top tier and last life
+    /// stage, as (situation id, choice, stat, required, ceiling). An any-of
+    /// group counts only when even its lowest minimum is above the ceiling.
+    pub fn unreachable_requirements(
+        &self,
+        ceiling: &RequirementCeiling,
+    ) -> Vec<(&str, ChoiceType, RequirementKey, u32, u32)> {
+        let mut found = Vec::new();
+        for situation in &self.situations {
+            let limit = ceiling.at(situation.tier_max, situation.life_stage_max);
+            for choice in &situation.choices {
+                let lowest_in_groups = choice.requirements.groups().filter_map(|(_, group)| {
+                    group.iter().map(|(&key, &value)| (key, value)).min_by_key(|&(_, v)| v)
+                });
+                for (key, value) in choice.requirements.iter().chain(lowest_in_groups) {
+                    if value > limit {
+                        found.push((situation.id.as_str(), choice.archetype, key, value, limit));
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Logs a warning for every requirement above `ceiling`
+    pub fn warn_unreachable_requirements(&self, ceiling: &RequirementCeiling) {
+        for (id, archetype, key, value, limit) in self.unreachable_requirements(ceiling) {
+            log::warn!(
+                "Situation '{}': {} choice requires {} {}, above the likely maximum of {}",
+                id,
+                archetype.as_str(),
+                key,
+                value,
+                limit
+            );
+        }
+    }
+
+    /// Looks up a situation by its id
+    pub fn situation_by_id(&self, id: &str) -> Option<&SituationTemplate> {
+        self.id_index.get(id).and_then(|&i| self.situations.get(i))
+    }
+
+    /// Every situation in stable load order
+    pub fn situations(&self) -> &[SituationTemplate] {
+        &self.situations
+    }
+}
+
+impl SituationTemplate {
+    /// Every authored line, tagged with the part it belongs to
+    fn authored_text(&self) -> HashSet<String> {
+        let fragments = &self.fragments;
+        let sections = [
+            ("opening", &fragments.openings),
+            ("conflict", &fragments.conflicts),
+            ("stakes", &fragments.stakes),
+        ];
+        let mut text: HashSet<String> = sections
+            .iter()
+            .flat_map(|(section, list)| {
+                list.iter().map(move |fragment| format!("{}: {}", section, fragment.text))
+            })
+            .collect();
+        for choice in &self.choices {
+            for line in &choice.text_fragments {
+                text.insert(format!("{}: {}", choice.archetype.as_str(), line));
+            }
+        }
+        text
+    }
+
+    /// Share of authored lines (openings, conflicts, stakes and choice text)
+    /// the two situations have in common, from 0 (none) to 1 (identical)
+    pub fn similarity(&self, other: &SituationTemplate) -> f32 {
+        let (mine, theirs) = (self.authored_text(), other.authored_text());
+        let union = mine.union(&theirs).count();
+        if union == 0 {
+            return 0.0;
+        }
+        mine.intersection(&theirs).count() as f32 / union as f32
+    }
 }
 
 // Helper struct for TOML deserialization
@@ -387,6 +681,13 @@ struct SituationConfig {
 }
 
 impl EventDomain {
+    pub const ALL: [EventDomain; 4] = [
+        EventDomain::Family,
+        EventDomain::Work,
+        EventDomain::Public,
+        EventDomain::Party,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             EventDomain::Family => "Family",
@@ -397,7 +698,33 @@ impl EventDomain {
     }
 }
 
+/// Inverse of `EventDomain::as_str`, also accepting the lowercase TOML spelling
+impl FromStr for EventDomain {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Family" | "family" => Ok(EventDomain::Family),
+            "Work" | "work" => Ok(EventDomain::Work),
+            "Public" | "public" => Ok(EventDomain::Public),
+            "Party" | "party" => Ok(EventDomain::Party),
+            _ => Err(anyhow!("Unknown event domain '{}'", s)),
+        }
+    }
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [Severity::Low, Severity::Medium, Severity::High];
+}
+
 impl ChoiceType {
+    pub const ALL: [ChoiceType; 4] = [
+        ChoiceType::Conform,
+        ChoiceType::Resist,
+        ChoiceType::Manipulate,
+        ChoiceType::Ignore,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             ChoiceType::Conform => "conform",
@@ -407,3 +734,31 @@ impl ChoiceType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_ids_fail_to_load() -> Result<()> {
+        let library = SituationLibrary::from_embedded_configs()?;
+        let mut situations = library.situations().to_vec();
+        situations.push(situations[0].clone());
+        let error = SituationLibrary::new(situations, library.variables).unwrap_err();
+        assert!(error.to_string().contains("more than once"), "{}", error);
+        Ok(())
+    }
+
+    #[test]
+    fn indices_follow_the_situations() -> Result<()> {
+        let library = SituationLibrary::from_embedded_configs()?;
+        for situation in library.situations() {
+            let found = library.situation_by_id(&situation.id).map(|s| s.domain);
+            assert_eq!(found, Some(situation.domain), "{}", situation.id);
+        }
+        let indexed: usize = library.by_domain.values().map(Vec::len).sum();
+        assert_eq!(indexed, library.situations().len());
+        assert!(library.situation_by_id("no_such_situation").is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Memory of the player's past procedural choices, recalled in callback
+//! fragments so later events can reference earlier decisions.
+
+use super::library::{ChoiceType, EventDomain};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// How many past choices are remembered
+pub const CHOICE_MEMORY_SIZE: usize = 10;
+
+/// Token in callback fragments replaced with a remembered choice
+pub const PAST_CHOICE_TOKEN: &str = "{past_choice}";
+
+/// Used in place of a callback when there is nothing to remember yet
+pub const NEUTRAL_MEMORY_PHRASE: &str = "You have no precedent to fall back on.";
+
+/// A past procedural decision
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ChoiceMemory {
+    pub archetype: ChoiceType,
+    pub domain: EventDomain,
+    pub turn: u64,
+}
+
+impl ChoiceMemory {
+    /// Past-tense phrase for `{past_choice}`, e.g. "pushed back at work"
+    pub fn describe(&self) -> String {
+        let action = match self.archetype {
+            ChoiceType::Conform => "went along with it",
+            ChoiceType::Resist => "pushed back",
+            ChoiceType::Manipulate => "worked the angles",
+            ChoiceType::Ignore => "looked the other way",
+        };
+        let setting = match self.domain {
+            EventDomain::Family => "with your family",
+            EventDomain::Work => "at work",
+            EventDomain::Public => "in public",
+            EventDomain::Party => "with the Party",
+        };
+        format!("{} {}", action, setting)
+    }
+}
+
+/// Most recent memory from the same domain, else the most recent overall
+pub fn recall(memory: &VecDeque<ChoiceMemory>, domain: EventDomain) -> Option<&ChoiceMemory> {
+    memory
+        .iter()
+        .rev()
+        .find(|m| m.domain == domain)
+        .or_else(|| memory.back())
+}
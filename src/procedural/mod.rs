@@ -1,8 +1,12 @@
+pub mod content_diff;
 pub mod generator;
 pub mod library;
+pub mod memory;
 pub mod risk_calculator;
 pub mod stat_calculator;
 pub mod text_assembly;
+pub mod time_context;
 
-pub use generator::generate_procedural_event;
+pub use generator::{AttemptTrace, generate_procedural_event, generate_samples};
 pub use library::{EventDomain, SituationLibrary};
+pub use text_assembly::{PlayerIdentity, Pronouns};
@@ -1,38 +1,300 @@
-use std::collections::HashMap;
+use crate::LotusApp;
+use crate::config::RiskClamp;
+use crate::requirements::{RequirementKey, Requirements};
+use serde::{Deserialize, Serialize};
+
+/// Risk added per point a requirement is above the player's stat
+const GAP_RISK_PER_POINT: u32 = 5;
+
+/// One adjustment to a choice's base risk, with the reason for it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RiskTerm {
+    pub amount: i32,
+    pub reason: String,
+}
+
+/// How a choice's risk was reached: base risk, each adjustment, then the
+/// domain's clamp
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RiskBreakdown {
+    pub base: u8,
+    pub terms: Vec<RiskTerm>,
+    pub clamp: RiskClamp,
+    pub risk: u8,
+}
+
+impl RiskBreakdown {
+    /// Base risk plus every adjustment, before clamping
+    pub fn unclamped(&self) -> i32 {
+        self.terms.iter().fold(self.base as i32, |risk, term| {
+            risk.saturating_add(term.amount)
+        })
+    }
+
+    /// The math in words, e.g. "Base 40 + 10 (2 short of guanxi_network) = 50"
+    pub fn describe(&self) -> String {
+        let mut text = format!("Base {}", self.base);
+        for term in &self.terms {
+            let sign = if term.amount < 0 { '-' } else { '+' };
+            text.push_str(&format!(
+                " {} {} ({})",
+                sign,
+                term.amount.unsigned_abs(),
+                term.reason
+            ));
+        }
+        let unclamped = self.unclamped();
+        text.push_str(&format!(" = {}", unclamped));
+        if unclamped > self.risk as i32 {
+            text.push_str(&format!(", capped at {}", self.risk));
+        } else if unclamped < self.risk as i32 {
+            text.push_str(&format!(", raised to {}", self.risk));
+        }
+        text
+    }
+}
 
 pub fn calculate_risk(
     base_risk: u8,
     risk_modifier: i8,
-    requirements: &HashMap<String, u32>,
+    requirements: &Requirements,
     player_state: &PlayerStats,
+    player_tier: usize,
+    tier_curve: &[i8],
+    clamp: RiskClamp,
 ) -> u8 {
-    let mut risk = base_risk as i32;
-
-    // Adjust based on requirement gaps
-    for (stat_name, &required_value) in requirements {
-        let player_value = match stat_name.as_str() {
-            "guanxi_family" => player_state.guanxi_family,
-            "guanxi_network" => player_state.guanxi_network,
-            "guanxi_party" => player_state.guanxi_party,
-            "career_level" => player_state.career_level,
-            _ => 0,
-        };
+    explain_risk(
+        base_risk,
+        risk_modifier,
+        requirements,
+        player_state,
+        player_tier,
+        tier_curve,
+        clamp,
+    )
+    .risk
+}
+
+/// The risk calculation itself, term by term; `calculate_risk` keeps only
+/// the result
+pub fn explain_risk(
+    base_risk: u8,
+    risk_modifier: i8,
+    requirements: &Requirements,
+    player_state: &PlayerStats,
+    player_tier: usize,
+    tier_curve: &[i8],
+    clamp: RiskClamp,
+) -> RiskBreakdown {
+    let mut terms = Vec::new();
+    let mut add = |amount: i32, reason: String| {
+        if amount != 0 {
+            terms.push(RiskTerm { amount, reason });
+        }
+    };
+
+    // Connections smooth things over, or standing draws scrutiny
+    add(
+        tier_risk_adjustment(tier_curve, player_tier),
+        "your tier".to_string(),
+    );
 
-        let gap = required_value.saturating_sub(player_value);
-        risk += (gap * 5) as i32;
+    // Adjust based on requirement gaps, in a stable order. Saturate so
+    // absurd requirement values clamp instead of overflowing.
+    let gap_for = |(key, required_value): (RequirementKey, u32)| {
+        (required_value.saturating_sub(player_state.get(key)), key)
+    };
+    let mut gaps: Vec<(u32, RequirementKey)> = requirements.iter().map(gap_for).collect();
+    gaps.sort_by_key(|(_, key)| key.as_str());
+    // A group only needs one member, so it costs the smallest of its gaps
+    for (_, group) in requirements.groups() {
+        let closest = group
+            .iter()
+            .map(|(&key, &value)| gap_for((key, value)))
+            .min_by_key(|&(gap, key)| (gap, key.as_str()));
+        gaps.extend(closest);
+    }
+    for (gap, stat_name) in gaps {
+        let gap_penalty = i32::try_from(gap.saturating_mul(GAP_RISK_PER_POINT)).unwrap_or(i32::MAX);
+        add(gap_penalty, format!("{} short of {}", gap, stat_name));
     }
 
     // Apply choice archetype modifier
-    risk += risk_modifier as i32;
+    add(risk_modifier as i32, "this approach".to_string());
+
+    let mut breakdown = RiskBreakdown {
+        base: base_risk,
+        terms,
+        clamp,
+        risk: 0,
+    };
+    // Clamp to the domain's range
+    breakdown.risk = breakdown
+        .unclamped()
+        .max(clamp.min as i32)
+        .min(clamp.max as i32) as u8;
+    breakdown
+}
 
-    // Clamp to 0-95 range
-    risk.clamp(0, 95) as u8
+/// Risk added at a player tier by a domain's tier curve. Tiers past the end
+/// of the curve use its last entry; an empty curve adds nothing.
+pub fn tier_risk_adjustment(tier_curve: &[i8], player_tier: usize) -> i32 {
+    tier_curve
+        .get(player_tier)
+        .or(tier_curve.last())
+        .map_or(0, |&adjustment| adjustment as i32)
 }
 
 // Helper struct to pass player stats
+#[derive(Debug, Clone, Copy)]
 pub struct PlayerStats {
     pub guanxi_family: u32,
     pub guanxi_network: u32,
     pub guanxi_party: u32,
     pub career_level: u32,
 }
+
+/// The single conversion from game state to the stats snapshot
+impl From<&LotusApp> for PlayerStats {
+    fn from(app: &LotusApp) -> Self {
+        Self {
+            guanxi_family: app.guanxi_family,
+            guanxi_network: app.guanxi_network,
+            guanxi_party: app.guanxi_party,
+            career_level: app.career_level,
+        }
+    }
+}
+
+impl PlayerStats {
+    /// The player's value for a requirement key. This is the one place
+    /// requirement keys map to stats.
+    pub fn get(&self, key: RequirementKey) -> u32 {
+        match key {
+            RequirementKey::GuanxiFamily => self.guanxi_family,
+            RequirementKey::GuanxiNetwork => self.guanxi_network,
+            RequirementKey::GuanxiParty => self.guanxi_party,
+            RequirementKey::CareerLevel => self.career_level,
+        }
+    }
+
+    /// True if every required stat is at or above its minimum, and so is at
+    /// least one stat in each any-of group
+    pub fn meets(&self, requirements: &Requirements) -> bool {
+        let meets = |(key, required_value): (RequirementKey, u32)| self.get(key) >= required_value;
+        requirements.iter().all(meets)
+            && requirements
+                .groups()
+                .all(|(_, group)| group.iter().map(|(&k, &v)| (k, v)).any(meets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::fixture::TEST_SEED;
+    use crate::procedural::EventDomain;
+    use anyhow::Result;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const NO_STATS: PlayerStats = PlayerStats {
+        guanxi_family: 0,
+        guanxi_network: 0,
+        guanxi_party: 0,
+        career_level: 0,
+    };
+
+    /// Risk stays within 0..=95 for any inputs, and a bigger requirement gap
+    /// never lowers it
+    #[test]
+    fn risk_is_bounded_and_rises_with_requirements() {
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        for _ in 0..10_000 {
+            let base_risk: u8 = rng.random();
+            let risk_modifier: i8 = rng.random();
+            let player_stats = PlayerStats {
+                guanxi_family: rng.random_range(0..=20),
+                guanxi_network: rng.random_range(0..=20),
+                guanxi_party: rng.random_range(0..=20),
+                career_level: rng.random_range(0..=20),
+            };
+            let mut requirements = Requirements::new();
+            for key in RequirementKey::ALL {
+                if rng.random_bool(0.5) {
+                    // Mostly plausible values, occasionally absurd ones
+                    let value = if rng.random_bool(0.05) {
+                        rng.random()
+                    } else {
+                        rng.random_range(0..=30)
+                    };
+                    requirements.insert(key, value);
+                }
+            }
+            let risk_with = |requirements: &Requirements| {
+                let clamp = RiskClamp::default();
+                calculate_risk(base_risk, risk_modifier, requirements, &player_stats, 0, &[], clamp)
+            };
+            let risk = risk_with(&requirements);
+            assert!(risk <= 95, "{} for {:?}", risk, requirements);
+
+            let key = RequirementKey::ALL[rng.random_range(0..RequirementKey::ALL.len())];
+            let mut harder = requirements.clone();
+            harder.insert(key, requirements.get(key).saturating_add(rng.random_range(1..=10)));
+            assert!(risk_with(&harder) >= risk, "raising {} in {:?}", key, requirements);
+        }
+    }
+
+    /// Maxed-out choices reach each domain's own ceiling, and a configured
+    /// floor lifts a riskless choice
+    #[test]
+    fn domains_clamp_to_their_own_range() -> Result<()> {
+        let mut config = GameConfig::from_embedded()?;
+        config.risk_clamps.insert(EventDomain::Family, RiskClamp { min: 10, max: 95 });
+        let risk = |domain: EventDomain, base_risk: u8, modifier: i8| {
+            let clamp = config.risk_clamp(domain);
+            calculate_risk(base_risk, modifier, &Requirements::new(), &NO_STATS, 0, &[], clamp)
+        };
+        let default_max = RiskClamp::default().max;
+        assert!(risk(EventDomain::Party, 95, 50) > default_max);
+        let public = risk(EventDomain::Public, 95, 50);
+        assert!(public < default_max);
+        assert_eq!(public, config.risk_clamp(EventDomain::Public).max);
+        assert_eq!(risk(EventDomain::Work, 95, 50), default_max);
+        assert_eq!(risk(EventDomain::Family, 0, -50), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn breakdown_reads_term_by_term() {
+        let player_stats = PlayerStats {
+            guanxi_network: 1,
+            ..NO_STATS
+        };
+        let requirements = Requirements::from_iter([(RequirementKey::GuanxiNetwork, 3)]);
+        let breakdown =
+            explain_risk(40, -5, &requirements, &player_stats, 0, &[], RiskClamp::default());
+        assert_eq!(
+            breakdown.describe(),
+            "Base 40 + 10 (2 short of guanxi_network) - 5 (this approach) = 45"
+        );
+        let capped =
+            explain_risk(90, 50, &Requirements::new(), &NO_STATS, 0, &[], RiskClamp::default());
+        assert!(capped.describe().ends_with("= 140, capped at 95"), "{}", capped.describe());
+    }
+
+    #[test]
+    fn tier_curves_shift_risk_by_tier() {
+        let risk_at = |tier: usize, curve: &[i8]| {
+            let clamp = RiskClamp::default();
+            calculate_risk(50, 0, &Requirements::new(), &NO_STATS, tier, curve, clamp)
+        };
+        let rising = [-5, 0, 5];
+        let falling = [5, 0, -5];
+        assert!(risk_at(2, &rising) > risk_at(0, &rising));
+        assert!(risk_at(2, &falling) < risk_at(0, &falling));
+        // Tiers past the end of a curve keep its last adjustment
+        assert_eq!(risk_at(crate::TIER_COUNT, &rising), risk_at(2, &rising));
+    }
+}
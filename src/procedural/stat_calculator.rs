@@ -39,3 +39,46 @@ pub fn calculate_failure_stats(success_stats: &StatProfile) -> StatProfile {
         guanxi_party_change: -success_stats.guanxi_party_change * 3 / 2,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TEST_SEED;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn fields(stats: &StatProfile) -> [(&'static str, i32); 6] {
+        [
+            ("scs_change", stats.scs_change),
+            ("finance_change", stats.finance_change),
+            ("career_level_change", stats.career_level_change),
+            ("guanxi_family_change", stats.guanxi_family_change),
+            ("guanxi_network_change", stats.guanxi_network_change),
+            ("guanxi_party_change", stats.guanxi_party_change),
+        ]
+    }
+
+    /// 1.5x the magnitude (truncated) the other way, and zero stays zero
+    #[test]
+    fn failure_stats_oppose_success() {
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        let mut samples = vec![StatProfile::default()];
+        for _ in 0..10_000 {
+            samples.push(StatProfile {
+                scs_change: rng.random_range(-1000..=1000),
+                finance_change: rng.random_range(-1000..=1000),
+                career_level_change: rng.random_range(-20..=20),
+                guanxi_family_change: rng.random_range(-20..=20),
+                guanxi_network_change: rng.random_range(-20..=20),
+                guanxi_party_change: rng.random_range(-20..=20),
+            });
+        }
+        for success in &samples {
+            let failure = calculate_failure_stats(success);
+            for ((name, s), (_, f)) in fields(success).into_iter().zip(fields(&failure)) {
+                let opposed = if s == 0 { f == 0 } else { s.signum() == -f.signum() };
+                assert!(opposed && f.abs() == s.abs() * 3 / 2, "{} {} failed as {}", name, s, f);
+            }
+        }
+    }
+}
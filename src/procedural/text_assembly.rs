@@ -1,63 +1,373 @@
-use super::library::{NarrativeFragments, VariableLibraries};
+use super::library::{Fragment, NarrativeFragments, SituationLibrary, VariableLibraries};
+use super::memory::{ChoiceMemory, NEUTRAL_MEMORY_PHRASE, PAST_CHOICE_TOKEN};
+use super::risk_calculator::PlayerStats;
+use super::time_context::TimeContext;
+use anyhow::{Context, Result, bail};
+use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
 use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// Pronoun set used for `{they}`/`{them}`/`{their}` tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Pronouns {
+    #[default]
+    They,
+    She,
+    He,
+}
+
+impl Pronouns {
+    pub const ALL: [Pronouns; 3] = [Pronouns::They, Pronouns::She, Pronouns::He];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Pronouns::They => "they/them",
+            Pronouns::She => "she/her",
+            Pronouns::He => "he/him",
+        }
+    }
+
+    fn subject(&self) -> &str {
+        match self {
+            Pronouns::They => "they",
+            Pronouns::She => "she",
+            Pronouns::He => "he",
+        }
+    }
+
+    fn object(&self) -> &str {
+        match self {
+            Pronouns::They => "them",
+            Pronouns::She => "her",
+            Pronouns::He => "him",
+        }
+    }
+
+    fn possessive(&self) -> &str {
+        match self {
+            Pronouns::They => "their",
+            Pronouns::She => "her",
+            Pronouns::He => "his",
+        }
+    }
+}
+
+/// The player's name and pronouns, substituted into `{player}` and pronoun tokens
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PlayerIdentity {
+    pub name: String,
+    pub pronouns: Pronouns,
+}
+
+pub const DEFAULT_PLAYER_NAME: &str = "Citizen";
+
+impl Default for PlayerIdentity {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PLAYER_NAME.to_string(),
+            pronouns: Pronouns::default(),
+        }
+    }
+}
+
+/// Everything placeholder substitution needs for one generated event. It
+/// remembers the value picked for each variable the first time it is
+/// resolved, so a placeholder repeated across the description, choices and
+/// result text reads the same everywhere in the event.
+pub struct SubstitutionContext<'a> {
+    variables: &'a VariableLibraries,
+    player_tier: usize,
+    identity: &'a PlayerIdentity,
+    time: Option<TimeContext>, // Biases `{*_time}` values when set
+    stats: Option<PlayerStats>, // Unlocks conditional fragments when set
+    values: HashMap<&'static str, String>,
+}
+
+impl<'a> SubstitutionContext<'a> {
+    pub fn new(
+        variables: &'a VariableLibraries,
+        player_tier: usize,
+        identity: &'a PlayerIdentity,
+    ) -> Self {
+        Self {
+            variables,
+            player_tier,
+            identity,
+            time: None,
+            stats: None,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Prefers `{*_time}` values that fit the given time of day and week
+    pub fn with_time(mut self, time: TimeContext) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Offers fragments whose `requires` these stats meet
+    pub fn with_stats(mut self, stats: PlayerStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+}
+
+/// Marks fragment boundaries while placeholders are resolved, so the
+/// finished text can be split back into its parts
+const FRAGMENT_BREAK: char = '\u{1f}';
+
+/// One resolved fragment of a description, labelled with its role
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DescriptionPart {
+    pub role: String, // "opening", "conflict", "stakes" or "callback"
+    pub text: String,
+}
+
+/// A fully resolved description along with the fragments it was built from
+#[derive(Debug, Clone)]
+pub struct AssembledDescription {
+    pub text: String,
+    pub parts: Vec<DescriptionPart>,
+}
+
+/// Broken content is an error in debug builds so it is caught before shipping;
+/// release builds only warn and show the text as-is.
+const STRICT_PLACEHOLDERS: bool = cfg!(debug_assertions);
 
 pub fn assemble_description(
+    situation_id: &str,
     fragments: &NarrativeFragments,
-    variables: &VariableLibraries,
-    player_tier: usize,
+    past_choice: Option<&ChoiceMemory>,
+    context: &mut SubstitutionContext,
+    rng: &mut impl Rng,
+) -> Result<AssembledDescription> {
+    let stats = context.stats.as_ref();
+    let openings = eligible_pool(&fragments.openings, stats);
+    let opening = choose_fragment(&openings, rng).expect("No opening fragments");
+    let conflicts = coherent_pool(eligible_pool(&fragments.conflicts, stats), opening);
+    let conflict = choose_fragment(&conflicts, rng).expect("No conflict fragments");
+    let stakes = coherent_pool(eligible_pool(&fragments.stakes, stats), opening);
+    let stakes = choose_fragment(&stakes, rng).expect("No stakes fragments");
+
+    let mut parts = vec![
+        ("opening", opening.text.clone()),
+        ("conflict", conflict.text.clone()),
+        ("stakes", stakes.text.clone()),
+    ];
+
+    // Situations with callbacks recall a past choice, or say there is none
+    let callbacks = eligible_pool(&fragments.callbacks, stats);
+    if let Some(callback) = choose_fragment(&callbacks, rng) {
+        let callback = match past_choice {
+            Some(memory) => callback.text.replace(PAST_CHOICE_TOKEN, &memory.describe()),
+            None => NEUTRAL_MEMORY_PHRASE.to_string(),
+        };
+        parts.push(("callback", callback));
+    }
+
+    // Resolve the fragments together so pronouns see the sentence before
+    // them, then split at the markers again
+    let separator = format!(" {}", FRAGMENT_BREAK);
+    let joined = parts
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join(&separator);
+    let resolved = resolve_placeholders(&joined, context, rng);
+    let text = check_resolved(resolved.replace(FRAGMENT_BREAK, ""), situation_id)?;
+    let parts = parts
+        .iter()
+        .zip(resolved.split(FRAGMENT_BREAK))
+        .map(|((role, _), text)| DescriptionPart {
+            role: role.to_string(),
+            text: text.trim().to_string(),
+        })
+        .collect();
+    Ok(AssembledDescription { text, parts })
+}
+
+/// Resolves every placeholder in a single template string: identity tokens
+/// first, then variable libraries. This is the whole substitution path with
+/// no fragment selection, so it can be driven directly with a seeded RNG.
+/// Variables already resolved in `context` keep their earlier value.
+pub fn resolve_placeholders(
+    text: &str,
+    context: &mut SubstitutionContext,
     rng: &mut impl Rng,
 ) -> String {
-    let opening = fragments
-        .openings
-        .choose(rng)
-        .expect("No opening fragments")
-        .clone();
-    let conflict = fragments
-        .conflicts
-        .choose(rng)
-        .expect("No conflict fragments")
-        .clone();
-    let stakes = fragments
-        .stakes
-        .choose(rng)
-        .expect("No stakes fragments")
-        .clone();
-
-    let mut text = format!("{} {} {}", opening, conflict, stakes);
-
-    // Variable substitution
-    text = substitute_variables(text, variables, player_tier, rng);
+    let text = substitute_identity(text, context.identity);
+    substitute_variables(text, context, rng)
+}
 
-    text
+/// Warns about any `{placeholder}` left in player-facing text for a situation,
+/// or fails when placeholder checks are strict.
+fn check_resolved(text: String, situation_id: &str) -> Result<String> {
+    let leftover = unresolved_placeholders(&text);
+    if leftover.is_empty() {
+        return Ok(text);
+    }
+    if STRICT_PLACEHOLDERS {
+        bail!(
+            "Situation '{}' has unresolved placeholders {:?} in: {}",
+            situation_id,
+            leftover,
+            text
+        );
+    }
+    log::warn!(
+        "Situation '{}' has unresolved placeholders {:?} in: {}",
+        situation_id,
+        leftover,
+        text
+    );
+    Ok(text)
 }
 
-pub fn assemble_choice_text(text_fragments: &[String], rng: &mut impl Rng) -> String {
-    text_fragments
-        .choose(rng)
-        .expect("No choice text fragments")
-        .clone()
+/// Returns the names of any `{placeholder}` tokens still present in the text
+pub fn unresolved_placeholders(text: &str) -> Vec<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// Narrows a fragment pool to those whose `requires` the player meets.
+/// Without stats only unconditional fragments qualify; if none qualify, the
+/// unconditional ones are used, and failing that the whole pool.
+fn eligible_pool<'a>(pool: &'a [Fragment], stats: Option<&PlayerStats>) -> Vec<&'a Fragment> {
+    let qualifies = |f: &Fragment| match stats {
+        Some(stats) => stats.meets(&f.requires),
+        None => f.requires.is_empty(),
+    };
+    let eligible: Vec<&Fragment> = pool.iter().filter(|f| qualifies(f)).collect();
+    if !eligible.is_empty() || pool.is_empty() {
+        return eligible;
+    }
+    let unconditional: Vec<&Fragment> = pool.iter().filter(|f| f.requires.is_empty()).collect();
+    if !unconditional.is_empty() {
+        return unconditional;
+    }
+    debug!("  No fragment conditions met, using unconstrained pool");
+    pool.iter().collect()
+}
+
+/// Narrows a fragment pool to those sharing a tag with the opening.
+/// Untagged openings, or pools without any matching tag, stay unconstrained.
+fn coherent_pool<'a>(pool: Vec<&'a Fragment>, opening: &Fragment) -> Vec<&'a Fragment> {
+    if !opening.tags.is_empty() {
+        let matching: Vec<&Fragment> =
+            pool.iter().copied().filter(|f| f.shares_tag_with(opening)).collect();
+        if !matching.is_empty() {
+            return matching;
+        }
+        debug!("  No fragments share tags {:?}, using unconstrained pool", opening.tags);
+    }
+    pool
+}
+
+/// Picks a fragment using its authored weight.
+/// Falls back to a uniform pick if every weight is zero.
+fn choose_fragment<'a>(fragments: &[&'a Fragment], rng: &mut impl Rng) -> Option<&'a Fragment> {
+    match WeightedIndex::new(fragments.iter().map(|f| f.weight)) {
+        Ok(dist) => Some(fragments[dist.sample(rng)]),
+        Err(_) => fragments.choose(rng).copied(),
+    }
+}
+
+pub fn assemble_choice_text(
+    situation_id: &str,
+    text_fragments: &[String],
+    context: &mut SubstitutionContext,
+    rng: &mut impl Rng,
+) -> Result<String> {
+    let text = text_fragments.choose(rng).expect("No choice text fragments");
+    let text = resolve_placeholders(text, context, rng);
+    check_resolved(text, situation_id)
+}
+
+/// Replaces `{player}`, `{they}`, `{them}` and `{their}` with the player's identity.
+/// Pronouns are capitalized when they start a sentence.
+pub fn substitute_identity(text: &str, identity: &PlayerIdentity) -> String {
+    let pronouns = identity.pronouns;
+    let tokens = [
+        ("{player}", identity.name.as_str()),
+        ("{they}", pronouns.subject()),
+        ("{them}", pronouns.object()),
+        ("{their}", pronouns.possessive()),
+    ];
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    'scan: while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        for (token, value) in tokens {
+            if rest[start..].starts_with(token) {
+                if starts_sentence(&result) {
+                    result.push_str(&capitalize(value));
+                } else {
+                    result.push_str(value);
+                }
+                rest = &rest[start + token.len()..];
+                continue 'scan;
+            }
+        }
+        // Not an identity token; leave it for other substitution passes
+        result.push('{');
+        rest = &rest[start + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// True if the next word written after `preceding` begins a sentence
+fn starts_sentence(preceding: &str) -> bool {
+    let preceding = preceding.trim_end_matches(|c: char| c.is_whitespace() || c == FRAGMENT_BREAK);
+    match preceding.chars().last() {
+        None => true,
+        Some(c) => matches!(c, '.' | '!' | '?'),
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 fn substitute_variables(
     mut text: String,
-    variables: &VariableLibraries,
-    player_tier: usize,
+    context: &mut SubstitutionContext,
     rng: &mut impl Rng,
 ) -> String {
     debug!("Starting variable substitution for text: {}", text);
+    let variables = context.variables;
+    let player_tier = context.player_tier;
 
-    // Helper macro to substitute a variable placeholder with a random choice from a list
+    // Helper macro to substitute a variable placeholder with a random choice from a list,
+    // or with the value it already has in this event
     macro_rules! substitute {
         ($placeholder:expr, $list:expr) => {
             if text.contains($placeholder) {
-                if $list.is_empty() {
+                if let Some(value) = context.values.get($placeholder) {
+                    debug!("  Reusing {} = '{}'", $placeholder, value);
+                    text = text.replace($placeholder, value);
+                } else if $list.is_empty() {
                     log::warn!("  Variable list for {} is EMPTY - cannot substitute", $placeholder);
                 } else {
                     debug!("  Variable list for {} has {} items", $placeholder, $list.len());
-                    if let Some(value) = $list.choose(rng) {
+                    let picked = match context.time {
+                        Some(time) if $placeholder.ends_with("_time}") => time.choose($list, rng),
+                        _ => $list.choose(rng),
+                    };
+                    if let Some(value) = picked {
                         debug!("  Replacing {} with '{}'", $placeholder, value);
                         text = text.replace($placeholder, value);
+                        context.values.insert($placeholder, value.clone());
                     }
                 }
             }
@@ -71,10 +381,8 @@ fn substitute_variables(
             .get(&player_tier.to_string())
             .or_else(|| variables.colleague_descriptors.get("2"))
             .expect("No colleague descriptors");
-        if let Some(descriptor) = descriptors.choose(rng) {
-            debug!("  Replacing {{colleague_descriptor}} with '{}' (tier {})", descriptor, player_tier);
-            text = text.replace("{colleague_descriptor}", descriptor);
-        }
+        debug!("  Using colleague descriptors for tier {}", player_tier);
+        substitute!("{colleague_descriptor}", descriptors);
     }
 
     // Work variables
@@ -142,21 +450,263 @@ fn substitute_variables(
     substitute!("{suspicious_behavior}", &variables.suspicious_behavior);
     substitute!("{survey_topic}", &variables.survey_topic);
 
-    // Check for any remaining unreplaced placeholders
-    if text.contains('{') && text.contains('}') {
-        let remaining_placeholders: Vec<&str> = text
-            .split('{')
-            .skip(1)
-            .filter_map(|s| s.split('}').next())
-            .collect();
-        if !remaining_placeholders.is_empty() {
-            log::warn!(
-                "Unreplaced placeholders found: {:?}",
-                remaining_placeholders
-            );
+    info!("Final text after substitution: {}", text);
+    text
+}
+
+// --- Golden descriptions ---
+
+pub const GOLDEN_DESCRIPTIONS_PATH: &str = "data/golden_descriptions.json";
+/// Set to regenerate the golden file after an intentional content change
+pub const UPDATE_GOLDENS_ENV: &str = "UPDATE_GOLDENS";
+const GOLDEN_SEED: u64 = 42;
+// One situation per domain plus a few with tier-specific variables
+const GOLDEN_SITUATION_IDS: [&str; 6] = [
+    "work_promotion_passed_over",
+    "work_bribery_opportunity",
+    "family_wedding_expense",
+    "party_denouncement_pressure",
+    "public_queue_conflict",
+    "public_surveillance_flagged",
+];
+
+/// One checked-in expected description
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct GoldenDescription {
+    id: String,
+    description: String,
+}
+
+/// Compares seeded descriptions for a fixed set of situations against the
+/// checked-in goldens and returns every mismatch. With `UPDATE_GOLDENS` set
+/// the file is rewritten instead.
+pub fn golden_description_mismatches(library: &SituationLibrary) -> Result<Vec<String>> {
+    let identity = PlayerIdentity::default();
+    let mut mismatches = Vec::new();
+    let mut actual = Vec::new();
+    for id in GOLDEN_SITUATION_IDS {
+        let Some(situation) = library.situation_by_id(id) else {
+            mismatches.push(format!("golden: situation '{}' no longer exists", id));
+            continue;
+        };
+        let mut rng = StdRng::seed_from_u64(GOLDEN_SEED);
+        let mut context =
+            SubstitutionContext::new(&library.variables, situation.tier_min, &identity);
+        let description =
+            assemble_description(&situation.id, &situation.fragments, None, &mut context, &mut rng)?
+                .text;
+        actual.push(GoldenDescription {
+            id: id.to_string(),
+            description,
+        });
+    }
+
+    if std::env::var_os(UPDATE_GOLDENS_ENV).is_some() {
+        fs::write(
+            GOLDEN_DESCRIPTIONS_PATH,
+            serde_json::to_string_pretty(&actual)? + "\n",
+        )?;
+        eprintln!("Updated {}", GOLDEN_DESCRIPTIONS_PATH);
+        return Ok(mismatches);
+    }
+
+    let golden_json = fs::read_to_string(GOLDEN_DESCRIPTIONS_PATH).with_context(|| {
+        format!(
+            "Failed to read {} (set {}=1 to create it)",
+            GOLDEN_DESCRIPTIONS_PATH, UPDATE_GOLDENS_ENV
+        )
+    })?;
+    let expected: Vec<GoldenDescription> = serde_json::from_str(&golden_json)?;
+    for golden in &expected {
+        match actual.iter().find(|a| a.id == golden.id) {
+            Some(a) if a.description != golden.description => mismatches.push(format!(
+                "golden: {} changed\n    expected: {}\n    actual:   {}",
+                golden.id, golden.description, a.description
+            )),
+            Some(_) => {}
+            None => mismatches.push(format!(
+                "golden: no description generated for {}",
+                golden.id
+            )),
         }
     }
+    if expected.len() != actual.len() {
+        mismatches.push(format!(
+            "golden: {} expected entries but {} generated",
+            expected.len(),
+            actual.len()
+        ));
+    }
+    Ok(mismatches)
+}
 
-    info!("Final text after substitution: {}", text);
-    text
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{TEST_SEED, test_variables};
+    use crate::requirements::{RequirementKey, Requirements};
+
+    fn fragment(text: &str, tags: &[&str]) -> Fragment {
+        Fragment {
+            text: text.to_string(),
+            weight: 1,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            requires: Requirements::new(),
+        }
+    }
+
+    fn texts<'a>(pool: &[&'a Fragment]) -> Vec<&'a str> {
+        pool.iter().map(|f| f.text.as_str()).collect()
+    }
+
+    fn needs_party(text: &str, guanxi_party: u32) -> Fragment {
+        let mut fragment = fragment(text, &[]);
+        fragment.requires.insert(RequirementKey::GuanxiParty, guanxi_party);
+        fragment
+    }
+
+    fn party_stats(guanxi_party: u32) -> PlayerStats {
+        PlayerStats {
+            guanxi_family: 0,
+            guanxi_network: 0,
+            guanxi_party,
+            career_level: 0,
+        }
+    }
+
+    /// A met condition offers its fragment alongside the unconditional ones
+    #[test]
+    fn met_conditions_join_the_pool() {
+        let pool = [needs_party("committee", 1), fragment("plain", &[])];
+        let eligible = eligible_pool(&pool, Some(&party_stats(1)));
+        assert_eq!(texts(&eligible), ["committee", "plain"]);
+        // Without stats only the unconditional fragment qualifies
+        assert_eq!(texts(&eligible_pool(&pool, None)), ["plain"]);
+    }
+
+    #[test]
+    fn unmet_conditions_leave_the_unconditional_fragments() {
+        let pool = [needs_party("committee", 3), fragment("plain", &[])];
+        let eligible = eligible_pool(&pool, Some(&party_stats(1)));
+        assert_eq!(texts(&eligible), ["plain"]);
+    }
+
+    /// With every fragment conditional and none met, the whole pool is used
+    /// rather than leave a description without that part
+    #[test]
+    fn all_unmet_conditions_use_the_whole_pool() {
+        let pool = [needs_party("committee", 3), needs_party("bureau", 5)];
+        let eligible = eligible_pool(&pool, Some(&party_stats(0)));
+        assert_eq!(texts(&eligible), ["committee", "bureau"]);
+    }
+
+    /// Conflicts and stakes follow a tagged opening's tone
+    #[test]
+    fn tagged_opening_keeps_to_its_tags() {
+        let opening = fragment("opening", &["grief", "family"]);
+        let pool = [
+            fragment("grief", &["grief"]),
+            fragment("anger", &["anger"]),
+            fragment("family", &["family", "anger"]),
+            fragment("untagged", &[]),
+        ];
+        let coherent = coherent_pool(pool.iter().collect(), &opening);
+        assert_eq!(texts(&coherent), ["grief", "family"]);
+    }
+
+    #[test]
+    fn untagged_opening_is_unconstrained() {
+        let opening = fragment("opening", &[]);
+        let pool = [fragment("grief", &["grief"]), fragment("untagged", &[])];
+        let coherent = coherent_pool(pool.iter().collect(), &opening);
+        assert_eq!(texts(&coherent), ["grief", "untagged"]);
+    }
+
+    /// A tag nothing else shares leaves the whole pool to pick from
+    #[test]
+    fn unmatched_tags_fall_back_to_the_whole_pool() {
+        let opening = fragment("opening", &["joy"]);
+        let pool = [fragment("grief", &["grief"]), fragment("untagged", &[])];
+        let coherent = coherent_pool(pool.iter().collect(), &opening);
+        assert_eq!(texts(&coherent), ["grief", "untagged"]);
+    }
+
+    #[test]
+    fn template_resolves_every_placeholder() {
+        let variables = test_variables();
+        let identity = PlayerIdentity::default();
+        let mut context = SubstitutionContext::new(&variables, 2, &identity);
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        let text = resolve_placeholders(
+            "{player} asked {colleague_descriptor} at {public_place} about {their} {excuse}.",
+            &mut context,
+            &mut rng,
+        );
+        assert!(!text.contains(['{', '}']), "{}", text);
+        assert!(text.starts_with(DEFAULT_PLAYER_NAME), "{}", text);
+        assert!(text.contains("the section chief") && text.contains("my bus broke down"));
+    }
+
+    /// Each tier draws its own descriptor, and a tier without a list uses tier 2's
+    #[test]
+    fn colleague_descriptor_follows_the_tier() {
+        let variables = test_variables();
+        let identity = PlayerIdentity::default();
+        let mut rng = StdRng::seed_from_u64(TEST_SEED);
+        for (tier, expected) in [
+            (0, "the new apprentice"),
+            (1, "the section chief"),
+            (2, "the section chief"),
+            (4, "the provincial director"),
+        ] {
+            let mut context = SubstitutionContext::new(&variables, tier, &identity);
+            let text = resolve_placeholders("{colleague_descriptor}", &mut context, &mut rng);
+            assert_eq!(text, expected, "tier {}", tier);
+        }
+    }
+
+    /// A placeholder repeated across an event's fragments, choices and
+    /// results resolves to one value
+    #[test]
+    fn repeated_placeholders_resolve_alike() -> Result<()> {
+        let variables = test_variables();
+        let identity = PlayerIdentity::default();
+        let fragments = NarrativeFragments {
+            openings: vec![fragment("A clerk slides {bribe_amount} across the desk.", &[])],
+            conflicts: vec![fragment("Nobody else saw the {bribe_amount}.", &[])],
+            stakes: vec![fragment("Is {bribe_amount} worth your record?", &[])],
+            callbacks: Vec::new(),
+        };
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut context = SubstitutionContext::new(&variables, 2, &identity);
+            let description =
+                assemble_description("test", &fragments, None, &mut context, &mut rng)?;
+            let amount = variables
+                .bribe_amount
+                .iter()
+                .find(|amount| description.parts[0].text.contains(amount.as_str()))
+                .expect("no bribe amount in the opening");
+            for part in &description.parts {
+                assert!(part.text.contains(amount.as_str()), "seed {}: {}", seed, part.text);
+            }
+            // Twice, so a value reused from the context is checked too
+            for _ in 0..2 {
+                let choice =
+                    resolve_placeholders("Take the {bribe_amount}.", &mut context, &mut rng);
+                assert_eq!(choice, format!("Take the {}.", amount), "seed {}", seed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Seeded descriptions still match data/golden_descriptions.json; rerun
+    /// with `UPDATE_GOLDENS=1` after an intentional text change
+    #[test]
+    fn descriptions_match_the_goldens() -> Result<()> {
+        let library = SituationLibrary::from_embedded_configs()?;
+        let mismatches = golden_description_mismatches(&library)?;
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+        Ok(())
+    }
 }
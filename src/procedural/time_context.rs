@@ -0,0 +1,160 @@
+//! In-game time of day and week, advancing with turns. Events pick
+//! `{*_time}` values that fit it, and scheming after hours is riskier.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// Turns per in-game day: morning, afternoon, evening
+const TURNS_PER_DAY: u64 = 3;
+/// Days per week; the last two are the weekend
+const DAYS_PER_WEEK: u64 = 7;
+const WEEKDAYS: u64 = 5;
+/// Chance a `{*_time}` placeholder uses a value that fits the time, when any does
+const TIME_MATCH_BIAS: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Morning,
+    Afternoon,
+    Evening,
+}
+
+impl TimeOfDay {
+    pub const ALL: [TimeOfDay; 3] = [TimeOfDay::Morning, TimeOfDay::Afternoon, TimeOfDay::Evening];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeOfDay::Morning => "morning",
+            TimeOfDay::Afternoon => "afternoon",
+            TimeOfDay::Evening => "evening",
+        }
+    }
+
+    /// Words in a variable value that place it at this time of day
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            TimeOfDay::Morning => &["morning"],
+            TimeOfDay::Afternoon => &["afternoon"],
+            TimeOfDay::Evening => &["evening", "night"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayKind {
+    Weekday,
+    Weekend,
+}
+
+impl DayKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayKind::Weekday => "weekday",
+            DayKind::Weekend => "weekend",
+        }
+    }
+
+    /// Words in a variable value that place it on this kind of day
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            DayKind::Weekday => &["monday", "tuesday", "wednesday", "thursday", "friday"],
+            DayKind::Weekend => &["saturday", "sunday", "weekend"],
+        }
+    }
+}
+
+/// When the current turn takes place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeContext {
+    pub time_of_day: TimeOfDay,
+    pub day: DayKind,
+}
+
+impl TimeContext {
+    /// Each turn moves on a third of a day; turn 0 is a Monday morning
+    pub fn for_turn(turn: u64) -> Self {
+        let time_of_day = TimeOfDay::ALL[(turn % TURNS_PER_DAY) as usize];
+        let day = if (turn / TURNS_PER_DAY) % DAYS_PER_WEEK < WEEKDAYS {
+            DayKind::Weekday
+        } else {
+            DayKind::Weekend
+        };
+        Self { time_of_day, day }
+    }
+
+    /// Short label for the UI, e.g. "Weekday evening"
+    pub fn describe(&self) -> String {
+        let day = self.day.as_str();
+        let mut label = day[..1].to_uppercase();
+        label.push_str(&day[1..]);
+        format!("{} {}", label, self.time_of_day.as_str())
+    }
+
+    /// Evenings and weekends, when fewer people are watching the office
+    pub fn after_hours(&self) -> bool {
+        self.time_of_day == TimeOfDay::Evening || self.day == DayKind::Weekend
+    }
+
+    /// True if `value` names this time and nothing that contradicts it
+    pub fn fits(&self, value: &str) -> bool {
+        let value = value.to_lowercase();
+        let mentions = |words: &[&str]| words.iter().any(|word| value.contains(word));
+
+        let other_times = TimeOfDay::ALL
+            .iter()
+            .filter(|time| **time != self.time_of_day)
+            .any(|time| mentions(time.keywords()));
+        let other_day = match self.day {
+            DayKind::Weekday => DayKind::Weekend,
+            DayKind::Weekend => DayKind::Weekday,
+        };
+        let names_now = mentions(self.time_of_day.keywords()) || mentions(self.day.keywords());
+        names_now && !other_times && !mentions(other_day.keywords())
+    }
+
+    /// Picks a value for a time placeholder, usually one that fits this time
+    pub fn choose<'v, R: Rng + ?Sized>(
+        &self,
+        list: &'v [String],
+        rng: &mut R,
+    ) -> Option<&'v String> {
+        let fitting: Vec<&String> = list.iter().filter(|value| self.fits(value)).collect();
+        if !fitting.is_empty() && rng.random_bool(TIME_MATCH_BIAS) {
+            return fitting.choose(rng).copied();
+        }
+        list.choose(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_runs_through_weekdays_then_a_weekend() {
+        for (turn, time_of_day, day) in [
+            (0, TimeOfDay::Morning, DayKind::Weekday),
+            (2, TimeOfDay::Evening, DayKind::Weekday),
+            (15, TimeOfDay::Morning, DayKind::Weekend),
+            (20, TimeOfDay::Evening, DayKind::Weekend),
+            (21, TimeOfDay::Morning, DayKind::Weekday),
+        ] {
+            let time = TimeContext::for_turn(turn);
+            assert_eq!((time.time_of_day, time.day), (time_of_day, day), "turn {}", turn);
+        }
+    }
+
+    #[test]
+    fn values_fit_only_the_times_they_name() {
+        let weekend_morning = TimeContext::for_turn(15);
+        for (value, expected) in [
+            ("Saturday morning", true),
+            ("this weekend", true),
+            ("Sunday evening", false),
+            ("Monday morning", false),
+            ("the team meeting", false),
+        ] {
+            assert_eq!(weekend_morning.fits(value), expected, "{}", value);
+        }
+    }
+}
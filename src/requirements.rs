@@ -0,0 +1,236 @@
+//! Stat requirements gating event options and choice archetypes.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A player stat an option can require. Serialized with the same snake_case
+/// strings the TOML, CSV, and JSON data have always used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementKey {
+    GuanxiFamily,
+    GuanxiNetwork,
+    GuanxiParty,
+    CareerLevel,
+}
+
+impl RequirementKey {
+    pub const ALL: [RequirementKey; 4] = [
+        RequirementKey::GuanxiFamily,
+        RequirementKey::GuanxiNetwork,
+        RequirementKey::GuanxiParty,
+        RequirementKey::CareerLevel,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequirementKey::GuanxiFamily => "guanxi_family",
+            RequirementKey::GuanxiNetwork => "guanxi_network",
+            RequirementKey::GuanxiParty => "guanxi_party",
+            RequirementKey::CareerLevel => "career_level",
+        }
+    }
+}
+
+impl fmt::Display for RequirementKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Minimums of which the player needs to meet just one
+pub type RequirementGroup = HashMap<RequirementKey, u32>;
+
+/// Minimum stat values, keyed by stat, all of which must be met. Named
+/// `any_of` groups each add one more condition: at least one of the group's
+/// minimums. Unknown keys are rejected when the data is loaded instead of
+/// silently counting as zero.
+///
+/// ```toml
+/// requirements = { career_level = 2, any_of = { connected = { guanxi_party = 3, guanxi_network = 3 } } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Requirements {
+    #[serde(flatten)]
+    all: HashMap<RequirementKey, u32>,
+    #[serde(default, deserialize_with = "non_empty_groups")]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    any_of: BTreeMap<String, RequirementGroup>,
+}
+
+/// An empty group could never be met, so it is a data error
+fn non_empty_groups<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, RequirementGroup>, D::Error> {
+    let groups = BTreeMap::<String, RequirementGroup>::deserialize(deserializer)?;
+    if let Some(name) = groups.iter().find(|(_, group)| group.is_empty()).map(|(name, _)| name) {
+        return Err(serde::de::Error::custom(format!(
+            "requirement group '{}' lists no stats",
+            name
+        )));
+    }
+    Ok(groups)
+}
+
+impl Requirements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: RequirementKey, value: u32) {
+        self.all.insert(key, value);
+    }
+
+    /// Adds (or replaces) the named group; an empty group is ignored
+    pub fn insert_group(&mut self, name: &str, group: RequirementGroup) {
+        if !group.is_empty() {
+            self.any_of.insert(name.to_string(), group);
+        }
+    }
+
+    /// Required value for the stat outside any group, or 0 when it is not required
+    pub fn get(&self, key: RequirementKey) -> u32 {
+        self.all.get(&key).copied().unwrap_or(0)
+    }
+
+    /// The minimums that must all be met
+    pub fn iter(&self) -> impl Iterator<Item = (RequirementKey, u32)> + '_ {
+        self.all.iter().map(|(&key, &value)| (key, value))
+    }
+
+    /// The any-of groups by name, in name order
+    pub fn groups(&self) -> impl Iterator<Item = (&str, &RequirementGroup)> + '_ {
+        self.any_of.iter().map(|(name, group)| (name.as_str(), group))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.all.is_empty() && self.any_of.is_empty()
+    }
+
+    /// Reads groups from the CSV `req_any_of` column: `name(key=value|key=value)`,
+    /// with several groups separated by `;`
+    pub fn parse_groups(&mut self, text: &str) -> Result<()> {
+        for spec in text.split(';').map(str::trim).filter(|spec| !spec.is_empty()) {
+            let Some((name, members)) = spec.strip_suffix(')').and_then(|s| s.split_once('('))
+            else {
+                bail!("requirement group '{}' should look like name(key=value|key=value)", spec);
+            };
+            let mut group = RequirementGroup::new();
+            for member in members.split('|').map(str::trim) {
+                let (key, value) = member
+                    .split_once('=')
+                    .with_context(|| format!("'{}' in group '{}' has no '='", member, name))?;
+                let key = RequirementKey::ALL
+                    .into_iter()
+                    .find(|k| k.as_str() == key.trim())
+                    .with_context(|| format!("unknown stat '{}' in group '{}'", key, name))?;
+                let value = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("bad minimum for {} in group '{}'", key, name))?;
+                group.insert(key, value);
+            }
+            self.insert_group(name.trim(), group);
+        }
+        Ok(())
+    }
+
+    /// The inverse of `parse_groups`, with stats in a stable order
+    pub fn format_groups(&self) -> String {
+        self.groups()
+            .map(|(name, group)| {
+                let mut members: Vec<_> = group.iter().collect();
+                members.sort_by_key(|(key, _)| key.as_str());
+                let members: Vec<String> = members
+                    .into_iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect();
+                format!("{}({})", name, members.join("|"))
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
+impl FromIterator<(RequirementKey, u32)> for Requirements {
+    fn from_iter<I: IntoIterator<Item = (RequirementKey, u32)>>(iter: I) -> Self {
+        Self {
+            all: iter.into_iter().collect(),
+            any_of: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskClamp;
+    use crate::procedural::risk_calculator::{PlayerStats, explain_risk};
+
+    #[derive(Deserialize)]
+    struct Choice {
+        requirements: Requirements,
+    }
+
+    fn grouped() -> Requirements {
+        let text = "requirements = { career_level = 2, any_of = { connected = { guanxi_party = 3, \
+                    guanxi_network = 4 }, kin = { guanxi_family = 1 } } }";
+        toml::from_str::<Choice>(text).unwrap().requirements
+    }
+
+    fn stats(career_level: u32, party: u32, network: u32, family: u32) -> PlayerStats {
+        PlayerStats {
+            guanxi_family: family,
+            guanxi_network: network,
+            guanxi_party: party,
+            career_level,
+        }
+    }
+
+    /// Every top-level minimum must be met, and each group by one member
+    #[test]
+    fn groups_need_one_member_each() {
+        let requirements = grouped();
+        for (player, expected) in [
+            (stats(2, 3, 0, 1), true),  // party covers the group
+            (stats(2, 0, 4, 1), true),  // so does network
+            (stats(2, 2, 3, 1), false), // neither connection is high enough
+            (stats(1, 3, 4, 1), false), // the AND part still applies
+            (stats(2, 3, 4, 0), false), // every group needs a member
+        ] {
+            assert_eq!(player.meets(&requirements), expected, "{:?}", player);
+        }
+    }
+
+    /// Short 1 of party and 3 of network: the group costs only the party gap
+    #[test]
+    fn group_risk_is_its_smallest_gap() {
+        let player = stats(2, 2, 1, 1);
+        let breakdown = explain_risk(20, 0, &grouped(), &player, 0, &[], RiskClamp::default());
+        let reasons: Vec<&str> = breakdown.terms.iter().map(|t| t.reason.as_str()).collect();
+        assert_eq!(reasons, ["1 short of guanxi_party"]);
+    }
+
+    #[test]
+    fn csv_groups_round_trip() -> Result<()> {
+        let requirements = grouped();
+        let mut parsed = Requirements::new();
+        parsed.insert(RequirementKey::CareerLevel, 2);
+        parsed.parse_groups(&requirements.format_groups())?;
+        assert_eq!(parsed, requirements);
+        assert!(Requirements::new().parse_groups("connected(guanxi_party=x)").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn empty_groups_and_unknown_stats_fail_to_load() {
+        for broken in [
+            "requirements = { any_of = { empty = {} } }",
+            "requirements = { any_of = { typo = { guanxi_partey = 1 } } }",
+        ] {
+            assert!(toml::from_str::<Choice>(broken).is_err(), "{}", broken);
+        }
+    }
+}
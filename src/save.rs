@@ -0,0 +1,239 @@
+//! Save games: the player's progress serialized to JSON.
+
+use super::{LotusApp, TIER_COUNT};
+use crate::game_data::EventData;
+use crate::lotus_widget::BoardView;
+use crate::procedural::library::ChoiceType;
+use crate::procedural::memory::ChoiceMemory;
+use crate::procedural::{EventDomain, PlayerIdentity};
+use crate::schema::{self, Migration};
+use crate::stats::{RunStats, StatSnapshot};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+pub const SAVE_PATH: &str = "lotus_save.json";
+
+/// Everything needed to resume a game. Content (events, situations, config)
+/// is reloaded from the embedded data rather than saved.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaveState {
+    pub version: u32, // Schema version, see `SAVE_VERSION`
+    pub player_identity: PlayerIdentity,
+    pub player_tier: usize,
+    pub player_petal: usize,
+    pub social_credit_score: i32,
+    pub finances: i32,
+    #[serde(default)]
+    pub legacy: u64,
+    pub career_level: u32,
+    pub guanxi_family: u32,
+    pub guanxi_network: u32,
+    pub guanxi_party: u32,
+    #[serde(default)]
+    pub favors: u32,
+    pub bankruptcy_pending: bool,
+    #[serde(default)]
+    pub used_grace: bool,
+    pub scs_since_review: i32,
+    pub player_age: u32,
+    pub life_stage: usize,
+    pub turn: u64,
+    pub current_event: Option<EventData>,
+    pub history: Vec<String>,
+    pub recent_event_domains: VecDeque<EventDomain>,
+    pub encounter_map: HashMap<String, u64>,
+    #[serde(default)]
+    pub handcrafted_seen: HashMap<String, u64>,
+    /// Written by older saves alongside `encounter_map`; only read
+    #[serde(default, skip_serializing)]
+    pub encounter_history: HashSet<String>,
+    #[serde(default)]
+    pub last_high_severity_turn: Option<u64>,
+    #[serde(default)]
+    pub choice_memory: VecDeque<ChoiceMemory>,
+    #[serde(default)]
+    pub choice_cooldowns: HashMap<String, HashMap<ChoiceType, u64>>,
+    #[serde(default)]
+    pub run_stats: RunStats,
+    #[serde(default)]
+    pub stat_history: VecDeque<StatSnapshot>,
+    #[serde(default)]
+    pub board_view: BoardView,
+}
+
+impl SaveState {
+    pub fn capture(app: &LotusApp) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            player_identity: app.player_identity.clone(),
+            player_tier: app.player_tier,
+            player_petal: app.player_petal,
+            social_credit_score: app.social_credit_score,
+            finances: app.finances,
+            legacy: app.legacy,
+            career_level: app.career_level,
+            guanxi_family: app.guanxi_family,
+            guanxi_network: app.guanxi_network,
+            guanxi_party: app.guanxi_party,
+            favors: app.favors,
+            bankruptcy_pending: app.bankruptcy_pending,
+            used_grace: app.used_grace,
+            scs_since_review: app.scs_since_review,
+            player_age: app.player_age,
+            life_stage: app.life_stage,
+            turn: app.turn,
+            current_event: app.current_event.clone(),
+            history: app.history.clone(),
+            recent_event_domains: app.recent_event_domains.clone(),
+            encounter_map: app.encounter_map.clone(),
+            handcrafted_seen: app.handcrafted_seen.clone(),
+            encounter_history: HashSet::new(),
+            last_high_severity_turn: app.last_high_severity_turn,
+            choice_memory: app.choice_memory.clone(),
+            choice_cooldowns: app.choice_cooldowns.clone(),
+            run_stats: app.run_stats.clone(),
+            stat_history: app.stat_history.clone(),
+            board_view: app.board_view,
+        }
+    }
+
+    pub fn restore(self, app: &mut LotusApp) {
+        app.player_identity = self.player_identity;
+        app.player_tier = self.player_tier.min(TIER_COUNT - 1);
+        app.player_petal = self.player_petal;
+        app.social_credit_score = self.social_credit_score;
+        app.finances = self.finances;
+        app.legacy = self.legacy;
+        app.career_level = self.career_level;
+        app.guanxi_family = self.guanxi_family;
+        app.guanxi_network = self.guanxi_network;
+        app.guanxi_party = self.guanxi_party;
+        app.favors = self.favors;
+        app.bankruptcy_pending = self.bankruptcy_pending;
+        app.used_grace = self.used_grace;
+        app.scs_since_review = self.scs_since_review;
+        app.player_age = self.player_age;
+        app.life_stage = self.life_stage;
+        app.turn = self.turn;
+        app.current_event = self.current_event;
+        app.history = self.history;
+        app.recent_event_domains = self.recent_event_domains;
+        app.encounter_map = self.encounter_map;
+        // Encounters an older save only listed are held back from its last turn
+        for id in self.encounter_history {
+            app.encounter_map.entry(id).or_insert(self.turn);
+        }
+        app.handcrafted_seen = self.handcrafted_seen;
+        app.last_high_severity_turn = self.last_high_severity_turn;
+        app.choice_memory = self.choice_memory;
+        app.choice_cooldowns = self.choice_cooldowns;
+        app.run_stats = self.run_stats;
+        app.stat_history = self.stat_history;
+        app.board_view = self.board_view;
+    }
+}
+
+pub fn save_exists() -> bool {
+    Path::new(SAVE_PATH).exists()
+}
+
+pub fn write_save(app: &LotusApp) -> Result<()> {
+    let json = serde_json::to_string_pretty(&SaveState::capture(app))?;
+    fs::write(SAVE_PATH, json).with_context(|| format!("Failed to write {}", SAVE_PATH))
+}
+
+pub fn read_save() -> Result<SaveState> {
+    let json =
+        fs::read_to_string(SAVE_PATH).with_context(|| format!("Failed to read {}", SAVE_PATH))?;
+    parse_save(&json).with_context(|| format!("Failed to parse {}", SAVE_PATH))
+}
+
+/// Migrations for save files; see `schema`
+const SAVE_MIGRATIONS: [Migration; 1] = [
+    // 0 -> 1: saves gained the version tag. Every field added to version 0
+    // saves has a serde default, so nothing else changes.
+    |_| Ok(()),
+];
+/// Schema version saves are written with
+pub const SAVE_VERSION: u32 = SAVE_MIGRATIONS.len() as u32;
+
+/// Parses a save of any known version, upgrading older ones
+pub fn parse_save(json: &str) -> Result<SaveState> {
+    let mut document: serde_json::Value = serde_json::from_str(json)?;
+    schema::upgrade(&mut document, &SAVE_MIGRATIONS, SAVE_PATH)?;
+    Ok(serde_json::from_value(document)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Fixture;
+
+    fn round_trip(app: &LotusApp) -> Result<SaveState> {
+        parse_save(&serde_json::to_string(&SaveState::capture(app))?)
+    }
+
+    #[test]
+    fn round_trip_keeps_view_grace_and_legacy() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.board_view = BoardView {
+            zoom: 1.75,
+            offset: [-40.0, 12.5],
+        };
+        app.used_grace = true;
+        app.legacy = 3000;
+        let reloaded = round_trip(&app)?;
+        assert_eq!(reloaded.board_view, app.board_view);
+        assert!(reloaded.used_grace);
+        assert_eq!(reloaded.legacy, 3000);
+        Ok(())
+    }
+
+    /// Saves from before the board view existed load fitted
+    #[test]
+    fn save_without_a_view_loads_fitted() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.board_view.zoom = 2.0;
+        let mut save = serde_json::to_value(SaveState::capture(&app))?;
+        if let Some(fields) = save.as_object_mut() {
+            fields.remove("board_view");
+        }
+        parse_save(&save.to_string())?.restore(&mut app);
+        assert_eq!(app.board_view, BoardView::default());
+        Ok(())
+    }
+
+    #[test]
+    fn versionless_save_upgrades() -> Result<()> {
+        let app = Fixture::new()?.build()?;
+        let mut save = serde_json::to_value(SaveState::capture(&app))?;
+        if let Some(fields) = save.as_object_mut() {
+            fields.remove("version");
+        }
+        assert_eq!(parse_save(&save.to_string())?.version, SAVE_VERSION);
+        Ok(())
+    }
+
+    /// An older save's `encounter_history` joins the map without moving the
+    /// turns the map already had
+    #[test]
+    fn old_encounter_history_merges_into_the_map() -> Result<()> {
+        let mut app = Fixture::new()?.build()?;
+        app.turn = 12;
+        app.encounter_map.insert("work_bribery_opportunity".to_string(), 10);
+        let mut save = serde_json::to_value(SaveState::capture(&app))?;
+        assert!(save.get("encounter_history").is_none());
+        save["encounter_history"] =
+            serde_json::json!(["work_bribery_opportunity", "family_wedding_expense"]);
+
+        let mut loaded = Fixture::new()?.build()?;
+        parse_save(&save.to_string())?.restore(&mut loaded);
+        assert_eq!(loaded.encounter_count(), 2);
+        assert_eq!(loaded.encounter_map["work_bribery_opportunity"], 10);
+        assert_eq!(loaded.encounter_map["family_wedding_expense"], 12);
+        Ok(())
+    }
+}
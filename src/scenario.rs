@@ -0,0 +1,121 @@
+//! Named starting scenarios, embedded from `data/scenarios.toml`.
+
+use anyhow::{Context, Result, ensure};
+use rand::Rng;
+use rand::distr::uniform::SampleUniform;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Petal a game starts on when the preset does not name one
+pub const DEFAULT_START_PETAL: usize = 1;
+
+/// A starting stat: either a fixed value or a `{ min = .., max = .. }` range
+/// rolled on the game RNG when the scenario is applied
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum StartingStat<T> {
+    Fixed(T),
+    Range { min: T, max: T },
+}
+
+impl<T: SampleUniform + PartialOrd + Copy + Display> StartingStat<T> {
+    /// The starting value; only ranges draw from the RNG
+    pub fn roll(&self, rng: &mut impl Rng) -> T {
+        match *self {
+            StartingStat::Fixed(value) => value,
+            StartingStat::Range { min, max } => rng.random_range(min..=max),
+        }
+    }
+
+    /// The value, if it isn't rolled
+    pub fn fixed(&self) -> Option<T> {
+        match *self {
+            StartingStat::Fixed(value) => Some(value),
+            StartingStat::Range { .. } => None,
+        }
+    }
+
+    /// The lowest value the stat can start at
+    pub fn min(&self) -> T {
+        match *self {
+            StartingStat::Fixed(value) | StartingStat::Range { min: value, .. } => value,
+        }
+    }
+
+    pub fn is_range(&self) -> bool {
+        matches!(self, StartingStat::Range { .. })
+    }
+
+    fn validate(&self, scenario: &str, stat: &str) -> Result<()> {
+        if let StartingStat::Range { min, max } = self {
+            ensure!(
+                min <= max,
+                "scenario '{}': {} range {}..{} is empty",
+                scenario,
+                stat,
+                min,
+                max
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Starting stats and board position for a new game
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScenarioPreset {
+    pub name: String,
+    pub description: String,
+    pub social_credit_score: StartingStat<i32>,
+    pub finances: StartingStat<i32>,
+    pub career_level: StartingStat<u32>,
+    pub guanxi_family: StartingStat<u32>,
+    pub guanxi_network: StartingStat<u32>,
+    pub guanxi_party: StartingStat<u32>,
+    pub player_age: u32,
+    /// Petal on the starting tier; must not be a review petal
+    #[serde(default = "default_start_petal")]
+    pub start_petal: usize,
+}
+
+impl ScenarioPreset {
+    /// True if any starting stat is a range
+    pub fn rolls_stats(&self) -> bool {
+        self.social_credit_score.is_range()
+            || self.finances.is_range()
+            || self.career_level.is_range()
+            || self.guanxi_family.is_range()
+            || self.guanxi_network.is_range()
+            || self.guanxi_party.is_range()
+    }
+}
+
+fn default_start_petal() -> usize {
+    DEFAULT_START_PETAL
+}
+
+#[derive(Deserialize)]
+struct ScenarioFile {
+    scenario: Vec<ScenarioPreset>,
+}
+
+/// Loads the scenario presets; the first one is the default selection
+pub fn load_scenarios() -> Result<Vec<ScenarioPreset>> {
+    let scenarios_toml = include_str!("../data/scenarios.toml");
+    let file: ScenarioFile =
+        toml::from_str(scenarios_toml).context("Failed to parse scenarios.toml")?;
+    ensure!(
+        !file.scenario.is_empty(),
+        "scenarios.toml defines no scenarios"
+    );
+    for scenario in &file.scenario {
+        let name = &scenario.name;
+        scenario.social_credit_score.validate(name, "social_credit_score")?;
+        scenario.finances.validate(name, "finances")?;
+        scenario.career_level.validate(name, "career_level")?;
+        scenario.guanxi_family.validate(name, "guanxi_family")?;
+        scenario.guanxi_network.validate(name, "guanxi_network")?;
+        scenario.guanxi_party.validate(name, "guanxi_party")?;
+    }
+    Ok(file.scenario)
+}
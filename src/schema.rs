@@ -0,0 +1,38 @@
+//! Version tags for the JSON files the game reads back (`events.json` and
+//! saves). Each file type keeps a list of migrations, where migration `i`
+//! upgrades a version `i` document to version `i + 1`; the current version
+//! is the number of migrations. Documents written before files carried a
+//! tag are version 0.
+
+use anyhow::{Result, ensure};
+use serde_json::Value;
+
+/// Upgrades a document in place, one version at a time
+pub type Migration = fn(&mut Value) -> Result<()>;
+
+/// Version a document declares, 0 when it has no `version` field
+pub fn document_version(document: &Value) -> u64 {
+    document.get("version").and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Runs every migration from the document's version up to the current one
+/// and stamps the result with the current version
+pub fn upgrade(document: &mut Value, migrations: &[Migration], what: &str) -> Result<()> {
+    let current = migrations.len();
+    let version = document_version(document) as usize;
+    ensure!(
+        version <= current,
+        "{} is version {}, newer than this build supports ({})",
+        what,
+        version,
+        current
+    );
+    for migrate in &migrations[version..] {
+        migrate(document)?;
+    }
+    let Some(fields) = document.as_object_mut() else {
+        anyhow::bail!("{} is not a JSON object after upgrading", what);
+    };
+    fields.insert("version".to_string(), current.into());
+    Ok(())
+}
@@ -0,0 +1,336 @@
+//! Opt-in dashboard summarising the player's own run: which choices they
+//! favour, how their gambles went and where their events came from, plus a
+//! graph of their stats turn by turn. It is built entirely from local game
+//! state and never leaves the machine.
+
+use super::LotusApp;
+use crate::number_format::{amount, signed_amount};
+use crate::procedural::EventDomain;
+use crate::procedural::library::ChoiceType;
+use eframe::egui::{self, Align2, Color32, FontId, ProgressBar, RichText, Sense, Stroke, Window};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Turns of stat history kept for the graph; older turns are dropped
+pub const STAT_HISTORY_SIZE: usize = 400;
+const GRAPH_SIZE: egui::Vec2 = egui::vec2(420.0, 200.0);
+const GRAPH_MARGIN: f32 = 36.0;
+
+/// Running totals for the current game, updated as each event resolves
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    pub archetypes: HashMap<ChoiceType, u32>,
+    pub domains: HashMap<EventDomain, u32>,
+    pub successes: u32, // Risky choices that paid off
+    pub failures: u32,
+    pub net_scs: i64,
+    pub net_finances: i64,
+}
+
+impl RunStats {
+    /// Counts a resolved choice. `succeeded` is `None` for choices without risk.
+    pub fn record_choice(
+        &mut self,
+        archetype: Option<ChoiceType>,
+        domain: Option<EventDomain>,
+        succeeded: Option<bool>,
+    ) {
+        if let Some(archetype) = archetype {
+            *self.archetypes.entry(archetype).or_default() += 1;
+        }
+        if let Some(domain) = domain {
+            *self.domains.entry(domain).or_default() += 1;
+        }
+        match succeeded {
+            Some(true) => self.successes += 1,
+            Some(false) => self.failures += 1,
+            None => {}
+        }
+    }
+
+    /// Adds the change an applied outcome actually made, after clamping
+    pub fn record_outcome(&mut self, scs_delta: i32, finance_delta: i32) {
+        self.net_scs += i64::from(scs_delta);
+        self.net_finances += i64::from(finance_delta);
+    }
+}
+
+/// Core stats as they stood at the end of a turn
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatSnapshot {
+    pub turn: u64,
+    pub age: u32,
+    pub scs: i32,
+    pub finances: i32,
+    pub career_level: u32,
+    pub guanxi_family: u32,
+    pub guanxi_network: u32,
+    pub guanxi_party: u32,
+}
+
+/// Which stat the graph plots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphStat {
+    #[default]
+    Scs,
+    Finances,
+    Career,
+    Guanxi, // Family, network and party summed
+}
+
+impl GraphStat {
+    pub const ALL: [GraphStat; 4] = [
+        GraphStat::Scs,
+        GraphStat::Finances,
+        GraphStat::Career,
+        GraphStat::Guanxi,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GraphStat::Scs => "SCS",
+            GraphStat::Finances => "Finances",
+            GraphStat::Career => "Career",
+            GraphStat::Guanxi => "Guanxi",
+        }
+    }
+
+    /// A plotted value as the status panel would show it
+    fn format(&self, value: f32) -> String {
+        match self {
+            GraphStat::Finances => amount(value.round() as i64),
+            _ => value.to_string(),
+        }
+    }
+
+    fn value(&self, snapshot: &StatSnapshot) -> f32 {
+        match self {
+            GraphStat::Scs => snapshot.scs as f32,
+            GraphStat::Finances => snapshot.finances as f32,
+            GraphStat::Career => snapshot.career_level as f32,
+            GraphStat::Guanxi => {
+                (snapshot.guanxi_family + snapshot.guanxi_network + snapshot.guanxi_party) as f32
+            }
+        }
+    }
+}
+
+impl LotusApp {
+    fn stat_snapshot(&self) -> StatSnapshot {
+        StatSnapshot {
+            turn: self.turn,
+            age: self.player_age,
+            scs: self.social_credit_score,
+            finances: self.finances,
+            career_level: self.career_level,
+            guanxi_family: self.guanxi_family,
+            guanxi_network: self.guanxi_network,
+            guanxi_party: self.guanxi_party,
+        }
+    }
+
+    /// Appends the current stats to the history, dropping the oldest turn
+    /// once it is full
+    pub(crate) fn record_stat_snapshot(&mut self) {
+        if self.stat_history.len() == STAT_HISTORY_SIZE {
+            self.stat_history.pop_front();
+        }
+        self.stat_history.push_back(self.stat_snapshot());
+    }
+
+    pub(crate) fn show_stat_graph(&mut self, ctx: &egui::Context) {
+        let mut open = self.stat_graph_open;
+        let mut stat = self.graph_stat;
+        Window::new(RichText::new("Trajectory").strong())
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for option in GraphStat::ALL {
+                        ui.selectable_value(&mut stat, option, option.as_str());
+                    }
+                });
+                ui.separator();
+                if self.stat_history.len() < 2 {
+                    ui.label(RichText::new("Play a turn to start the graph.").weak());
+                    return;
+                }
+                draw_graph(ui, &self.stat_history, stat);
+            });
+        self.stat_graph_open = open;
+        self.graph_stat = stat;
+    }
+
+    pub(crate) fn show_stats_dashboard(&mut self, ctx: &egui::Context) {
+        let stats = &self.run_stats;
+        let mut open = self.stats_open;
+        Window::new(RichText::new("Your Run").strong())
+            .open(&mut open)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                ui.label(format!("Net SCS: {:+}", stats.net_scs));
+                ui.label(format!(
+                    "Net finances: {} ¥",
+                    signed_amount(stats.net_finances)
+                ));
+
+                ui.separator();
+                ui.label(RichText::new("Risky choices").strong());
+                let risky = stats.successes + stats.failures;
+                if risky == 0 {
+                    ui.label(RichText::new("None taken yet.").weak());
+                } else {
+                    let rate = stats.successes as f32 / risky as f32;
+                    ui.add(ProgressBar::new(rate).text(format!(
+                        "{} succeeded, {} failed ({:.0}%)",
+                        stats.successes,
+                        stats.failures,
+                        rate * 100.0
+                    )));
+                }
+
+                ui.separator();
+                ui.label(RichText::new("Choices").strong());
+                let archetypes: Vec<(&str, u32)> = ChoiceType::ALL
+                    .iter()
+                    .map(|archetype| {
+                        let count = stats.archetypes.get(archetype).copied().unwrap_or(0);
+                        (archetype.as_str(), count)
+                    })
+                    .collect();
+                show_bars(ui, &archetypes);
+
+                ui.separator();
+                ui.label(RichText::new("Event domains").strong());
+                let domains: Vec<(&str, u32)> = EventDomain::ALL
+                    .iter()
+                    .map(|domain| {
+                        let count = stats.domains.get(domain).copied().unwrap_or(0);
+                        (domain.as_str(), count)
+                    })
+                    .collect();
+                show_bars(ui, &domains);
+            });
+        self.stats_open = open;
+    }
+}
+
+/// One bar per row, scaled to the largest count
+fn show_bars(ui: &mut egui::Ui, rows: &[(&str, u32)]) {
+    let total: u32 = rows.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        ui.label(RichText::new("Nothing recorded yet.").weak());
+        return;
+    }
+    // Non-zero, since the total is
+    let max = rows.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    for (label, count) in rows {
+        let share = *count as f32 / total as f32 * 100.0;
+        ui.add(
+            ProgressBar::new(*count as f32 / max as f32)
+                .text(format!("{}: {} ({:.0}%)", label, count, share)),
+        );
+    }
+}
+
+/// Line graph of `stat` over the snapshots, with turn and age along the
+/// bottom. Hovering shows the value at that turn.
+fn draw_graph<'a>(
+    ui: &mut egui::Ui,
+    history: impl IntoIterator<Item = &'a StatSnapshot>,
+    stat: GraphStat,
+) {
+    let snapshots: Vec<&StatSnapshot> = history.into_iter().collect();
+    let (response, painter) = ui.allocate_painter(GRAPH_SIZE, Sense::hover());
+    let rect = response.rect;
+    let plot = egui::Rect::from_min_max(
+        rect.min + egui::vec2(GRAPH_MARGIN, 4.0),
+        rect.max - egui::vec2(4.0, GRAPH_MARGIN),
+    );
+    let visuals = ui.visuals();
+    let text_color = visuals.weak_text_color();
+    let font = FontId::proportional(11.0);
+
+    let values: Vec<f32> = snapshots
+        .iter()
+        .map(|snapshot| stat.value(snapshot))
+        .collect();
+    let low = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let high = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    // A flat line still gets some height
+    let (low, high) = if high - low < 1.0 {
+        (low - 1.0, high + 1.0)
+    } else {
+        (low, high)
+    };
+    let last = (snapshots.len() - 1) as f32;
+    let point = |i: usize, value: f32| {
+        egui::pos2(
+            plot.left() + plot.width() * i as f32 / last,
+            plot.bottom() - plot.height() * (value - low) / (high - low),
+        )
+    };
+
+    painter.rect_stroke(
+        plot,
+        0.0,
+        Stroke::new(1.0, text_color),
+        egui::StrokeKind::Inside,
+    );
+    painter.text(
+        plot.left_top() - egui::vec2(4.0, 0.0),
+        Align2::RIGHT_TOP,
+        stat.format(high),
+        font.clone(),
+        text_color,
+    );
+    painter.text(
+        plot.left_bottom() - egui::vec2(4.0, 0.0),
+        Align2::RIGHT_BOTTOM,
+        stat.format(low),
+        font.clone(),
+        text_color,
+    );
+    if let (Some(first), Some(latest)) = (snapshots.first(), snapshots.last()) {
+        for (snapshot, pos, align) in [
+            (first, plot.left_bottom(), Align2::LEFT_TOP),
+            (latest, plot.right_bottom(), Align2::RIGHT_TOP),
+        ] {
+            painter.text(
+                pos + egui::vec2(0.0, 4.0),
+                align,
+                format!("turn {}\nage {}", snapshot.turn, snapshot.age),
+                font.clone(),
+                text_color,
+            );
+        }
+    }
+
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| point(i, value))
+        .collect();
+    let line_color = Color32::from_rgb(218, 165, 32);
+    painter.add(egui::Shape::line(points, Stroke::new(2.0, line_color)));
+
+    if let Some(hover) = response.hover_pos()
+        && plot.x_range().contains(hover.x)
+    {
+        let i = (((hover.x - plot.left()) / plot.width()) * last).round() as usize;
+        let i = i.min(snapshots.len() - 1);
+        let at = point(i, values[i]);
+        painter.circle_filled(at, 4.0, line_color);
+        painter.text(
+            at - egui::vec2(0.0, 8.0),
+            Align2::CENTER_BOTTOM,
+            format!(
+                "{} (turn {}, age {})",
+                stat.format(values[i]),
+                snapshots[i].turn, snapshots[i].age
+            ),
+            font,
+            visuals.strong_text_color(),
+        );
+    }
+}